@@ -1,47 +1,376 @@
-use proc_macro::{TokenStream, TokenTree, Literal, Group, Delimiter, Punct, Spacing};
+use proc_macro::{TokenStream, TokenTree, Literal, Group, Delimiter, Punct, Spacing, Span, Ident};
+use std::iter;
 
-enum State {
-    Start, Work, Sep, Stop
+//{{{ Leonardo numbers
+// Unsigned integer types the terms may be emitted as, with their widths;
+// `usize` has one table per possible pointer width.
+const TYPES: [(&str, &[u32]); 6] = [
+    ("u8", &[8]), ("u16", &[16]), ("u32", &[32]), ("u64", &[64]), ("u128", &[128]),
+    ("usize", &[16, 32, 64]),
+];
+
+// The recurrence `L(n) = L(n - 1) + L(n - 2) + offset` from `seeds`, cut at
+// `max`, and the type of its terms.
+struct LeonSpec {
+    ty     : Ident,
+    seeds  : (u128, u128),
+    offset : u128,
+    max    : Option<(u128, Span)>,
 }
 
-struct LeonSeq {
-    a: usize,
-    b: usize,
-    s: State,
+impl LeonSpec {
+    // Terms up to `max` and up to the largest value on `width` bits.
+    fn terms(&self, width: u32) -> Vec<u128> {
+        let limit = self.max.map_or(u128::MAX, |(max, _)| max).min(u128::MAX >> (128 - width));
+        let (mut a, mut b) = self.seeds;
+        let mut terms = vec![];
+        while a <= limit {
+            terms.push(a);
+            let Some(c) = a.checked_add(b).and_then(|c| c.checked_add(self.offset)) else {
+                if b <= limit { terms.push(b); }
+                break;
+            };
+            (a, b) = (b, c);
+        }
+        terms
+    }
 }
 
-impl LeonSeq {
-    fn new(a: usize, b: usize) -> Self {
-        LeonSeq { a, b, s: State::Start }
+// `::core::compile_error!("msg")`, reported at `span`.
+fn error(span: Span, msg: &str) -> TokenStream {
+    let msg = TokenTree::Literal(Literal::string(msg)).into();
+    "::core::compile_error!".parse::<TokenStream>().unwrap().into_iter()
+        .chain(iter::once(Group::new(Delimiter::Parenthesis, msg).into()))
+        .map(|mut tt| { tt.set_span(span); tt })
+        .collect()
+}
+
+// An unsigned integer literal without suffix.
+fn parse_int(tt: Option<&TokenTree>, what: &str) -> Result<(u128, Span), (Span, String)> {
+    let expected = |span| Err((span, format!("expected an unsigned integer literal as {}", what)));
+    match tt {
+        Some(TokenTree::Literal(lit)) => {
+            let text = lit.to_string().replace('_', "");
+            if !text.bytes().all(|c| c.is_ascii_digit()) { return expected(lit.span()); }
+            text.parse().map(|x| (x, lit.span()))
+                .map_err(|_| (lit.span(), format!("{} does not fit in u128", what)))
+        },
+        Some(tt) => expected(tt.span()),
+        None => expected(Span::call_site()),
     }
 }
 
-impl Iterator for LeonSeq {
-    type Item = TokenTree;
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.s {
-            State::Start => {
-                self.s = State::Sep;
-                Some(TokenTree::Literal(Literal::usize_suffixed(self.a)))
-            },
-            State::Work => {
-                let (c, o) = self.b.overflowing_add(self.a + 1);
-                (self.a, self.b) = (self.b, c);
-                self.s = if o { State::Stop } else { State::Sep };
-                Some(TokenTree::Literal(Literal::usize_suffixed(self.a)))
-            },
-            State::Sep => {
-                self.s = State::Work;
-                Some(TokenTree::Punct(Punct::new(',', Spacing::Alone)))
+fn parse_leon(input: TokenStream) -> Result<LeonSpec, (Span, String)> {
+    let tokens = input.into_iter().collect::<Vec<_>>();
+    let mut args = tokens.split(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ','))
+        .peekable();
+    let mut spec = LeonSpec {
+        ty: Ident::new("usize", Span::call_site()), seeds: (1, 1), offset: 1, max: None,
+    };
+    let mut seen = vec![];
+    let mut first = true;
+    while let Some(arg) = args.next() {
+        // No argument at all, or a trailing comma.
+        if arg.is_empty() && args.peek().is_none() { break; }
+        let Some(key) = arg.first() else {
+            return Err((Span::call_site(), "expected an argument before `,`".into()));
+        };
+        let TokenTree::Ident(key) = key else {
+            return Err((key.span(), "expected a type or `seeds`, `offset` or `max`".into()));
+        };
+        let name = key.to_string();
+        if first && arg.len() == 1 {
+            first = false;
+            if !TYPES.iter().any(|&(ty, _)| ty == name) {
+                return Err((key.span(), format!("expected an unsigned integer type, found `{}`",
+                                                name)));
+            }
+            spec.ty = key.clone();
+            continue;
+        }
+        first = false;
+        if seen.contains(&name) {
+            return Err((key.span(), format!("`{}` given twice", name)));
+        }
+        match arg.get(1) {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => (),
+            Some(tt) => return Err((tt.span(), format!("expected `=` after `{}`", name))),
+            None => return Err((key.span(), format!("expected `=` after `{}`", name))),
+        }
+        let value = &arg[2 ..];
+        match name.as_str() {
+            "seeds" => {
+                let group = match value.first() {
+                    Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => g,
+                    tt => return Err((tt.map_or(key.span(), TokenTree::span),
+                                      "expected a pair `(a, b)` of seeds".into())),
+                };
+                let pair = group.stream().into_iter().collect::<Vec<_>>();
+                let comma = pair.get(1).filter(|tt| matches!(tt, TokenTree::Punct(p)
+                                                              if p.as_char() == ','));
+                if pair.len() != 3 || comma.is_none() {
+                    return Err((group.span(), "expected a pair `(a, b)` of seeds".into()));
+                }
+                spec.seeds = (parse_int(pair.first(), "seed")?.0, parse_int(pair.get(2), "seed")?.0);
             },
-            State::Stop => None,
+            "offset" => spec.offset = parse_int(value.first(), "offset")?.0,
+            "max" => spec.max = Some(parse_int(value.first(), "max")?),
+            _ => return Err((key.span(), format!("unknown argument `{}`, expected `seeds`, \
+                                                  `offset` or `max`", name))),
+        }
+        if let Some(tt) = value.get(1) {
+            return Err((tt.span(), format!("unexpected token after `{}`", name)));
+        }
+        seen.push(name);
+    }
+    let widths = TYPES.iter().find(|&&(ty, _)| spec.ty.to_string() == ty).unwrap().1;
+    let width = *widths.last().unwrap();
+    if let Some((max, span)) = spec.max {
+        if width < 128 && max >> width != 0 {
+            return Err((span, format!("max does not fit in {}", spec.ty)));
         }
     }
+    if spec.seeds == (0, 0) && spec.offset == 0 {
+        return Err((Span::call_site(), "the sequence is constant zero".into()));
+    }
+    Ok(spec)
+}
+
+// `[t0, t1, …]` with every term suffixed by the type of `spec`.
+fn leon_array(spec: &LeonSpec, terms: &[u128]) -> TokenTree {
+    let ty = spec.ty.to_string();
+    let body = terms.iter().flat_map(|&t| {
+        let lit = format!("{}{}", t, ty).parse::<Literal>().unwrap();
+        [TokenTree::Literal(lit), punct(',')]
+    }).collect();
+    Group::new(Delimiter::Bracket, body).into()
+}
+
+/// `gen_leonardo_ind!()` expands to the array of all Leonardo numbers
+/// `L(0) = L(1) = 1, L(n) = L(n - 1) + L(n - 2) + 1` which fit in `usize`.
+///
+/// The arguments, all optional, change the sequence:
+/// `gen_leonardo_ind!(u64, seeds = (1, 1), offset = 1, max = 1000)` emits
+/// the terms as `u64` (or any unsigned integer type) from the seeds `L(0)`
+/// and `L(1)`, adding `offset` to each sum, up to `max`. The `usize` terms
+/// are cut at the pointer width of the target, not of the host.
+#[proc_macro]
+pub fn gen_leonardo_ind(input: TokenStream) -> TokenStream {
+    let spec = match parse_leon(input) {
+        Ok(spec) => spec,
+        Err((span, msg)) => return error(span, &msg),
+    };
+    let widths = TYPES.iter().find(|&&(ty, _)| spec.ty.to_string() == ty).unwrap().1;
+    let tables = widths.iter().map(|&width| (width, spec.terms(width))).collect::<Vec<_>>();
+    if tables.iter().all(|(_, terms)| terms == &tables[0].1) {
+        return leon_array(&spec, &tables[0].1).into();
+    }
+    // `{ #[cfg(target_pointer_width = "w")] const LEON: [usize; k] = […]; … LEON }`
+    let mut body = TokenStream::new();
+    for (width, terms) in &tables {
+        let item = format!("#[cfg(target_pointer_width = \"{}\")] const LEON: [{}; {}] =",
+                           width, spec.ty, terms.len());
+        body.extend(item.parse::<TokenStream>().unwrap());
+        body.extend([leon_array(&spec, terms), punct(';')]);
+    }
+    body.extend(iter::once(TokenTree::Ident(Ident::new("LEON", Span::call_site()))));
+    TokenTree::Group(Group::new(Delimiter::Brace, body)).into()
+}
+//}}}
+//{{{ Sorting networks
+// Optimal (n <= 10) or best known (n > 10) sorting networks on `n` wires,
+// as layers of comparators `(i, j)` with `i < j`.
+const NETWORKS: [&[&[(usize, usize)]]; 17] = [
+    // 0
+    &[],
+    // 1
+    &[],
+    // 2: 1 comparator, 1 layer
+    &[
+        &[(0, 1)],
+    ],
+    // 3: 3 comparators, 3 layers
+    &[
+        &[(0, 2)],
+        &[(0, 1)],
+        &[(1, 2)],
+    ],
+    // 4: 5 comparators, 3 layers
+    &[
+        &[(0, 2), (1, 3)],
+        &[(0, 1), (2, 3)],
+        &[(1, 2)],
+    ],
+    // 5: 9 comparators, 5 layers
+    &[
+        &[(0, 3), (1, 4)],
+        &[(0, 2), (1, 3)],
+        &[(0, 1), (2, 4)],
+        &[(1, 2), (3, 4)],
+        &[(2, 3)],
+    ],
+    // 6: 12 comparators, 5 layers
+    &[
+        &[(0, 5), (1, 3), (2, 4)],
+        &[(1, 2), (3, 4)],
+        &[(0, 3), (2, 5)],
+        &[(0, 1), (2, 3), (4, 5)],
+        &[(1, 2), (3, 4)],
+    ],
+    // 7: 16 comparators, 6 layers
+    &[
+        &[(0, 6), (2, 3), (4, 5)],
+        &[(0, 2), (1, 4), (3, 6)],
+        &[(0, 1), (2, 5), (3, 4)],
+        &[(1, 2), (4, 6)],
+        &[(2, 3), (4, 5)],
+        &[(1, 2), (3, 4), (5, 6)],
+    ],
+    // 8: 19 comparators, 6 layers
+    &[
+        &[(0, 2), (1, 3), (4, 6), (5, 7)],
+        &[(0, 4), (1, 5), (2, 6), (3, 7)],
+        &[(0, 1), (2, 3), (4, 5), (6, 7)],
+        &[(2, 4), (3, 5)],
+        &[(1, 4), (3, 6)],
+        &[(1, 2), (3, 4), (5, 6)],
+    ],
+    // 9: 25 comparators, 7 layers
+    &[
+        &[(0, 3), (1, 7), (2, 5), (4, 8)],
+        &[(0, 7), (2, 4), (3, 8), (5, 6)],
+        &[(0, 2), (1, 3), (4, 5), (7, 8)],
+        &[(1, 4), (3, 6), (5, 7)],
+        &[(0, 1), (2, 4), (3, 5), (6, 8)],
+        &[(2, 3), (4, 5), (6, 7)],
+        &[(1, 2), (3, 4), (5, 6)],
+    ],
+    // 10: 29 comparators, 8 layers
+    &[
+        &[(0, 8), (1, 9), (2, 7), (3, 5), (4, 6)],
+        &[(0, 2), (1, 4), (5, 8), (7, 9)],
+        &[(0, 3), (2, 4), (5, 7), (6, 9)],
+        &[(0, 1), (3, 6), (8, 9)],
+        &[(1, 5), (2, 3), (4, 8), (6, 7)],
+        &[(1, 2), (3, 5), (4, 6), (7, 8)],
+        &[(2, 3), (4, 5), (6, 7)],
+        &[(3, 4), (5, 6)],
+    ],
+    // 11: 35 comparators, 8 layers
+    &[
+        &[(0, 9), (1, 6), (2, 4), (3, 7), (5, 8)],
+        &[(0, 1), (3, 5), (4, 10), (6, 9), (7, 8)],
+        &[(1, 3), (2, 5), (4, 7), (8, 10)],
+        &[(0, 4), (1, 2), (3, 7), (5, 9), (6, 8)],
+        &[(0, 1), (2, 6), (4, 5), (7, 8), (9, 10)],
+        &[(2, 4), (3, 6), (5, 7), (8, 9)],
+        &[(1, 2), (3, 4), (5, 6), (7, 8)],
+        &[(2, 3), (4, 5), (6, 7)],
+    ],
+    // 12: 39 comparators, 9 layers
+    &[
+        &[(0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9)],
+        &[(0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11)],
+        &[(0, 2), (1, 6), (5, 10), (9, 11)],
+        &[(0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10)],
+        &[(1, 4), (3, 5), (6, 8), (7, 10)],
+        &[(1, 3), (2, 5), (6, 9), (8, 10)],
+        &[(2, 3), (4, 5), (6, 7), (8, 9)],
+        &[(4, 6), (5, 7)],
+        &[(3, 4), (5, 6), (7, 8)],
+    ],
+    // 13: 45 comparators, 10 layers
+    &[
+        &[(0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8)],
+        &[(1, 6), (2, 3), (4, 11), (7, 9), (8, 10)],
+        &[(0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12)],
+        &[(4, 6), (5, 9), (8, 11), (10, 12)],
+        &[(0, 5), (3, 8), (4, 7), (6, 11), (9, 10)],
+        &[(0, 1), (2, 5), (6, 9), (7, 8), (10, 11)],
+        &[(1, 3), (2, 4), (5, 6), (9, 10)],
+        &[(1, 2), (3, 4), (5, 7), (6, 8)],
+        &[(2, 3), (4, 5), (6, 7), (8, 9)],
+        &[(3, 4), (5, 6)],
+    ],
+    // 14: 51 comparators, 10 layers
+    &[
+        &[(0, 13), (1, 12), (2, 6), (3, 4), (5, 9), (7, 8)],
+        &[(0, 7), (1, 2), (4, 11), (6, 12), (8, 13), (9, 10)],
+        &[(0, 1), (2, 3), (4, 6), (5, 7), (8, 9), (10, 11), (12, 13)],
+        &[(2, 8), (3, 9), (4, 5), (6, 7), (10, 12), (11, 13)],
+        &[(1, 10), (2, 4), (3, 5), (6, 8), (7, 9), (11, 12)],
+        &[(0, 4), (3, 6), (5, 8), (7, 11), (9, 12)],
+        &[(0, 2), (1, 4), (7, 10), (9, 11)],
+        &[(1, 3), (4, 6), (5, 7), (8, 10)],
+        &[(1, 2), (3, 4), (5, 6), (7, 8), (9, 10)],
+        &[(4, 5), (6, 7)],
+    ],
+    // 15: 56 comparators, 10 layers
+    &[
+        &[(0, 11), (1, 14), (2, 13), (3, 7), (4, 5), (6, 10), (8, 9)],
+        &[(0, 6), (1, 8), (2, 3), (5, 12), (7, 13), (9, 14), (10, 11)],
+        &[(1, 2), (3, 4), (5, 7), (6, 8), (9, 10), (11, 12), (13, 14)],
+        &[(0, 2), (3, 9), (4, 10), (5, 6), (7, 8), (11, 13), (12, 14)],
+        &[(0, 1), (2, 11), (3, 5), (4, 6), (7, 9), (8, 10), (12, 13)],
+        &[(0, 3), (1, 5), (4, 7), (6, 9), (8, 12), (10, 13)],
+        &[(1, 3), (2, 5), (8, 11), (10, 12)],
+        &[(2, 4), (5, 7), (6, 8), (9, 11)],
+        &[(2, 3), (4, 5), (6, 7), (8, 9), (10, 11)],
+        &[(5, 6), (7, 8)],
+    ],
+    // 16: 60 comparators, 10 layers
+    &[
+        &[(0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10)],
+        &[(0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12)],
+        &[(0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15)],
+        &[(0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15)],
+        &[(1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14)],
+        &[(1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14)],
+        &[(2, 4), (3, 6), (9, 12), (11, 13)],
+        &[(3, 5), (6, 8), (7, 9), (10, 12)],
+        &[(3, 4), (5, 6), (7, 8), (9, 10), (11, 12)],
+        &[(6, 7), (8, 9)],
+    ],
+];
+
+fn punct(c: char) -> TokenTree {
+    TokenTree::Punct(Punct::new(c, Spacing::Alone))
+}
+
+// `&[item, item, …]`
+fn slice_ref<I>(items: I) -> impl Iterator<Item = TokenTree>
+where I: IntoIterator<Item = TokenStream> {
+    let mut body = TokenStream::new();
+    for item in items {
+        body.extend(item);
+        body.extend(iter::once(punct(',')));
+    }
+    [punct('&'), TokenTree::Group(Group::new(Delimiter::Bracket, body))].into_iter()
 }
 
+/// `gen_sorting_network!(n)` expands to the layers of the best sorting
+/// network known on `n <= 16` wires, as a `&[&[(usize, usize)]]`.
 #[proc_macro]
-pub fn gen_leonardo_ind(_: TokenStream) -> TokenStream {
-    TokenStream::from(TokenTree::Group(Group::new(
-        Delimiter::Bracket, LeonSeq::new(1, 1).collect()
-    )))
+pub fn gen_sorting_network(input: TokenStream) -> TokenStream {
+    let (n, span) = match input.into_iter().collect::<Vec<_>>().as_slice() {
+        [TokenTree::Literal(lit)] => (lit.to_string().parse::<usize>().ok(), lit.span()),
+        [TokenTree::Literal(_), tt, ..] => {
+            return error(tt.span(), "unexpected tokens after the number of wires");
+        },
+        [tt, ..] => (None, tt.span()),
+        [] => (None, Span::call_site()),
+    };
+    let Some(layers) = n.and_then(|n| NETWORKS.get(n)) else {
+        let msg = format!("expected a number of wires up to {}", NETWORKS.len() - 1);
+        return error(span, &msg);
+    };
+    slice_ref(layers.iter().map(|layer| {
+        slice_ref(layer.iter().map(|&(i, j)| {
+            let pair = [i, j].into_iter().flat_map(|ind| {
+                [TokenTree::Literal(Literal::usize_suffixed(ind)), punct(',')]
+            }).collect();
+            TokenTree::Group(Group::new(Delimiter::Parenthesis, pair)).into()
+        })).collect()
+    })).collect()
 }
+//}}}
@@ -0,0 +1,39 @@
+use macro_leon::gen_leonardo_ind;
+
+#[test]
+fn test_leonardo_default() {
+    const LEON: &[usize] = &gen_leonardo_ind!();
+    assert_eq!(LEON[.. 8], [1, 1, 3, 5, 9, 15, 25, 41]);
+    assert!(LEON.windows(3).all(|w| w[2] == w[1] + w[0] + 1));
+    let (a, b) = (LEON[LEON.len() - 2], LEON[LEON.len() - 1]);
+    assert!(a.checked_add(b).and_then(|c| c.checked_add(1)).is_none());
+}
+
+#[test]
+fn test_leonardo_types() {
+    let leon: &[u8] = &gen_leonardo_ind!(u8);
+    assert_eq!(leon, [1, 1, 3, 5, 9, 15, 25, 41, 67, 109, 177]);
+    let leon: &[u32] = &gen_leonardo_ind!(u32,);
+    assert_eq!(*leon.last().unwrap(), 3_672_623_805);
+    let leon: &[u128] = &gen_leonardo_ind!(u128);
+    assert!(leon.windows(3).all(|w| w[2] == w[1] + w[0] + 1));
+    assert!(leon.last().unwrap() > &u64::MAX.into());
+}
+
+#[test]
+fn test_leonardo_sequences() {
+    // Fibonacci numbers.
+    let fib: &[u64] = &gen_leonardo_ind!(u64, seeds = (0, 1), offset = 0, max = 100);
+    assert_eq!(fib, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89]);
+    let leon: &[u16] = &gen_leonardo_ind!(u16, max = 1_000, offset = 1);
+    assert_eq!(leon, [1, 1, 3, 5, 9, 15, 25, 41, 67, 109, 177, 287, 465, 753]);
+    let leon: &[u32] = &gen_leonardo_ind!(u32, seeds = (2, 3), offset = 2, max = 50);
+    assert_eq!(leon, [2, 3, 7, 12, 21, 35]);
+    let leon: &[u8] = &gen_leonardo_ind!(u8, seeds = (5, 1), max = 3);
+    assert!(leon.is_empty());
+}
+
+#[test]
+fn test_leonardo_errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
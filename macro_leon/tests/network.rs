@@ -0,0 +1,16 @@
+use macro_leon::gen_sorting_network;
+
+#[test]
+fn test_sorting_network() {
+    const NETWORK: &[&[(usize, usize)]] = gen_sorting_network!(4);
+    assert_eq!(NETWORK, [&[(0, 2), (1, 3)][..], &[(0, 1), (2, 3)], &[(1, 2)]]);
+    let network: &[&[(usize, usize)]] = gen_sorting_network!(0);
+    assert!(network.is_empty());
+    let network: &[&[(usize, usize)]] = gen_sorting_network!(16);
+    assert_eq!(network.iter().map(|layer| layer.len()).sum::<usize>(), 60);
+}
+
+#[test]
+fn test_sorting_network_errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/network/*.rs");
+}
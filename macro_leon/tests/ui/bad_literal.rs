@@ -0,0 +1,5 @@
+use macro_leon::gen_leonardo_ind;
+
+const LEON: &[u64] = &gen_leonardo_ind!(u64, offset = 1.5);
+
+fn main() {}
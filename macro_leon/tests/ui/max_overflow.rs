@@ -0,0 +1,5 @@
+use macro_leon::gen_leonardo_ind;
+
+const LEON: &[u8] = &gen_leonardo_ind!(u8, max = 256);
+
+fn main() {}
@@ -0,0 +1,5 @@
+use macro_leon::gen_leonardo_ind;
+
+const LEON: &[u64] = &gen_leonardo_ind!(u64, max 10);
+
+fn main() {}
@@ -0,0 +1,5 @@
+use macro_leon::gen_sorting_network;
+
+const NETWORK: &[&[(usize, usize)]] = gen_sorting_network!(4, 5);
+
+fn main() {}
@@ -0,0 +1,5 @@
+use macro_leon::gen_leonardo_ind;
+
+const LEON: &[usize] = &gen_leonardo_ind!(step = 2);
+
+fn main() {}
@@ -0,0 +1,14 @@
+//{{{ Insertion sort
+use crate::hole::Hole;
+pub(crate) fn insertion_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    for ind in 1 .. data.len() {
+        if !is_less(&data[ind], &data[ind - 1]) { continue; }
+        let mut hole = Hole::new(&mut data[..= ind], ind);
+        hole.move_to(ind - 1);
+        while hole.pos() > 0 && is_less(hole.element(), hole.get(hole.pos() - 1)) {
+            hole.move_to(hole.pos() - 1);
+        }
+    }
+}
+//}}}
@@ -0,0 +1,105 @@
+//{{{ Intro sort
+use std::cmp::Ordering;
+use std::mem;
+use crate::heap::heap_sort_impl;
+use super::insertion::insertion_sort_impl;
+
+const INSERTION_THRESHOLD: usize = 16;
+const NINTHER_THRESHOLD: usize = 128;
+
+fn sort3<T, F>(data: &mut [T], a: usize, b: usize, c: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    if is_less(&data[b], &data[a]) { data.swap(a, b); }
+    if is_less(&data[c], &data[b]) {
+        data.swap(b, c);
+        if is_less(&data[b], &data[a]) { data.swap(a, b); }
+    }
+}
+
+/// Moves the median of three, or of three medians of three for long slices,
+/// to the front.
+pub(crate) fn choose_pivot<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    let (a, b, c) = (n / 4, n / 2, n / 4 * 3);
+    if n > NINTHER_THRESHOLD {
+        sort3(data, a - 1, a, a + 1, is_less);
+        sort3(data, b - 1, b, b + 1, is_less);
+        sort3(data, c - 1, c, c + 1, is_less);
+    }
+    sort3(data, a, b, c, is_less);
+    data.swap(0, b);
+}
+
+// Partitions `data` around `data[0]` and returns the final index of the pivot.
+// Both scans stop at elements equal to the pivot, so runs of equal elements
+// are split evenly.
+fn partition<T, F>(data: &mut [T], is_less: &mut F) -> usize
+where F: FnMut(&T, &T) -> bool {
+    let (mut ind_l, mut ind_r) = (1, data.len() - 1);
+    loop {
+        while ind_l <= ind_r && is_less(&data[ind_l], &data[0]) { ind_l += 1; }
+        while ind_l <= ind_r && is_less(&data[0], &data[ind_r]) { ind_r -= 1; }
+        if ind_l >= ind_r { break; }
+        data.swap(ind_l, ind_r);
+        ind_l += 1;
+        ind_r -= 1;
+    }
+    data.swap(0, ind_r);
+    ind_r
+}
+
+pub(crate) fn intro_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    fn intro_sort<T, F>(mut data: &mut [T], mut limit: u32, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        loop {
+            if data.len() <= INSERTION_THRESHOLD {
+                insertion_sort_impl(data, is_less);
+                return;
+            }
+            if limit == 0 {
+                heap_sort_impl(data, is_less);
+                return;
+            }
+            limit -= 1;
+            choose_pivot(data, is_less);
+            let ind = partition(data, is_less);
+            let (data1, data2) = mem::take(&mut data).split_at_mut(ind);
+            let data2 = &mut data2[1 ..];
+            if data1.len() < data2.len() {
+                intro_sort(data1, limit, is_less);
+                data = data2;
+            } else {
+                intro_sort(data2, limit, is_less);
+                data = data1;
+            }
+        }
+    }
+    let limit = 2 * (usize::BITS - data.len().leading_zeros());
+    intro_sort(data, limit, is_less);
+}
+
+/// Sorts `data` with intro sort: quick sort with median of three (or ninther)
+/// pivots, which falls back to heap sort once the recursion gets deeper than
+/// `2 log2 n` and finishes short slices with insertion sort.
+///
+/// Runs in `O(n log n)` in the worst case and recurses at most `O(log n)`
+/// deep.
+pub fn intro_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    intro_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with intro sort according to `compare`.
+pub fn intro_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    intro_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with intro sort by the key extracted with `f`.
+pub fn intro_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    intro_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
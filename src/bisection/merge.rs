@@ -0,0 +1,196 @@
+//{{{ Merge sort
+//{{{ Merge sort, serial
+use std::cmp::Ordering;
+use std::ptr;
+use super::insertion::insertion_sort_impl;
+
+// Runs up to this length are sorted by insertion, which is stable unlike the
+// fixed networks of `small`.
+const INSERTION_THRESHOLD: usize = 16;
+
+// The gap `v1[ind_rd1 .. ind_rd1 + ind_rd2]` left by the elements of the
+// second run which still wait in `v2[.. ind_rd2]`. Dropping it fills the gap,
+// so each element is in `v1` exactly once even if `is_less` panics.
+struct MergeHole<T> {
+    v1      : *mut T,
+    v2      : *const T,
+    ind_rd1 : usize,
+    ind_rd2 : usize,
+}
+
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::copy_nonoverlapping(self.v2, self.v1.add(self.ind_rd1), self.ind_rd2);
+        }
+    }
+}
+
+// Merges the sorted runs `v1[.. mid]` and `v1[mid ..]`, moving the second one
+// out into the spare capacity of `v2` first.
+fn merge_sorted_array<T, F>(v1: &mut [T], mid: usize, v2: &mut Vec<T>, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let len2 = v1.len() - mid;
+    assert!(v2.is_empty() && v2.capacity() >= len2);
+    let v1 = v1.as_mut_ptr();
+    let v2 = v2.as_mut_ptr();
+    unsafe {
+        ptr::copy_nonoverlapping(v1.add(mid), v2, len2);
+        let mut hole = MergeHole { v1, v2, ind_rd1: mid, ind_rd2: len2 };
+        while hole.ind_rd1 > 0 && hole.ind_rd2 > 0 {
+            let ind_wr = hole.ind_rd1 + hole.ind_rd2 - 1;
+            // Ties go to the second run, which is written first from the back.
+            if is_less(&*v2.add(hole.ind_rd2 - 1), &*v1.add(hole.ind_rd1 - 1)) {
+                hole.ind_rd1 -= 1;
+                ptr::copy_nonoverlapping(v1.add(hole.ind_rd1), v1.add(ind_wr), 1);
+            } else {
+                hole.ind_rd2 -= 1;
+                ptr::copy_nonoverlapping(v2.add(hole.ind_rd2), v1.add(ind_wr), 1);
+            }
+        }
+    }
+}
+
+pub(crate) fn merge_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    fn merge_sort<T, F>(data: &mut [T], buf: &mut Vec<T>, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        if data.len() <= INSERTION_THRESHOLD {
+            insertion_sort_impl(data, is_less);
+            return;
+        }
+        let mid = data.len().div_ceil(2);
+        let (data1, data2) = data.split_at_mut(mid);
+        merge_sort(data1, buf, is_less);
+        merge_sort(data2, buf, is_less);
+        merge_sorted_array(data, mid, buf, is_less);
+    }
+    let mut buf = Vec::with_capacity(data.len() / 2);
+    merge_sort(data, &mut buf, is_less);
+}
+
+/// Sorts `data` with top-down merge sort, which is stable.
+///
+/// Runs up to 16 elements are sorted by insertion rather than by the
+/// networks of [`small`](crate::small), which would break stability.
+pub fn merge_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    merge_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with top-down merge sort according to `compare`.
+pub fn merge_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    merge_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with top-down merge sort by the key extracted with `f`.
+pub fn merge_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    merge_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
+//{{{ Merge sort, parallel
+use std::mem::MaybeUninit;
+use crate::pool;
+
+// Slices up to this length are sorted and merged on a single thread.
+const SEQUENTIAL_THRESHOLD: usize = 1 << 12;
+
+// Number of elements of `v1` among the first `k` elements of the stable
+// merge of `v1` and `v2`, found by binary search (co-ranking).
+fn co_rank<T, F>(k: usize, v1: &[T], v2: &[T], is_less: &F) -> usize
+where F: Fn(&T, &T) -> bool {
+    let (mut ind_l, mut ind_r) = (k.saturating_sub(v2.len()), k.min(v1.len()));
+    while ind_l < ind_r {
+        let ind = ind_l + (ind_r - ind_l) / 2;
+        if !is_less(&v2[k - ind - 1], &v1[ind]) {
+            ind_l = ind + 1;
+        } else {
+            ind_r = ind;
+        }
+    }
+    ind_l
+}
+
+// Merges `v1` and `v2` into `out` by bitwise copies, splitting `out` in
+// halves at the co-rank of its middle for the first `t_depth` levels. The
+// elements stay owned by `v1` and `v2`.
+fn merge_into<T, F>(v1: &mut [T], v2: &mut [T], out: &mut [MaybeUninit<T>],
+                    t_depth: u32, is_less: &F)
+where T: Send, F: Fn(&T, &T) -> bool + Sync {
+    if t_depth == 0 || out.len() <= SEQUENTIAL_THRESHOLD {
+        let (mut ind1, mut ind2) = (0, 0);
+        for slot in out.iter_mut() {
+            let x = if ind2 < v2.len() && (ind1 == v1.len() || is_less(&v2[ind2], &v1[ind1])) {
+                ind2 += 1;
+                &v2[ind2 - 1]
+            } else {
+                ind1 += 1;
+                &v1[ind1 - 1]
+            };
+            slot.write(unsafe { ptr::read(x) });
+        }
+        return;
+    }
+    let k = out.len() / 2;
+    let ind1 = co_rank(k, v1, v2, is_less);
+    let (v11, v12) = v1.split_at_mut(ind1);
+    let (v21, v22) = v2.split_at_mut(k - ind1);
+    let (out1, out2) = out.split_at_mut(k);
+    pool::join(|| merge_into(v11, v21, out1, t_depth - 1, is_less),
+               || merge_into(v12, v22, out2, t_depth - 1, is_less));
+}
+
+fn merge_p_sort_impl<T, F>(data: &mut [T], t_depth: u32, is_less: &F)
+where T: Send, F: Fn(&T, &T) -> bool + Sync {
+    // A panic of `is_less` in a merge leaves `data` untouched by it, since
+    // the merged run is copied back from `buf` only once complete.
+    fn merge_sort<T, F>(data: &mut [T], buf: &mut [MaybeUninit<T>], t_depth: u32, is_less: &F)
+    where T: Send, F: Fn(&T, &T) -> bool + Sync {
+        let n = data.len();
+        if t_depth == 0 || n <= SEQUENTIAL_THRESHOLD {
+            merge_sort_impl(data, &mut &*is_less);
+            return;
+        }
+        let mid = n.div_ceil(2);
+        {
+            let (data1, data2) = data.split_at_mut(mid);
+            let (buf1, buf2) = buf.split_at_mut(mid);
+            pool::join(|| merge_sort(data1, buf1, t_depth - 1, is_less),
+                       || merge_sort(data2, buf2, t_depth - 1, is_less));
+        }
+        let (data1, data2) = data.split_at_mut(mid);
+        merge_into(data1, data2, buf, t_depth, is_less);
+        unsafe { ptr::copy_nonoverlapping(buf.as_ptr() as *const T, data.as_mut_ptr(), n); }
+    }
+    let mut buf = Vec::with_capacity(data.len());
+    merge_sort(data, &mut buf.spare_capacity_mut()[.. data.len()], t_depth, is_less);
+}
+
+/// Sorts `data` with top-down merge sort, forking the first `t_depth` levels
+/// of recursion onto separate threads. Stable like [`merge_sort`].
+///
+/// The merges are parallel as well: the output of a merge is cut in halves,
+/// the matching split of both runs is found by binary search (co-ranking, or
+/// merge path), and both halves are merged concurrently, down to `t_depth`
+/// levels. Slices below a few thousand elements stay on one thread. Uses a
+/// scratch buffer of `n` elements.
+pub fn merge_p_sort<T>(data: &mut [T], t_depth: u32)
+where T: PartialOrd + Send {
+    merge_p_sort_impl(data, t_depth, &T::lt);
+}
+
+/// Parallel [`merge_sort_by`] on `2^t_depth` threads.
+pub fn merge_p_sort_by<T, F>(data: &mut [T], t_depth: u32, compare: F)
+where T: Send, F: Fn(&T, &T) -> Ordering + Sync {
+    merge_p_sort_impl(data, t_depth, &|a: &T, b: &T| compare(a, b) == Ordering::Less);
+}
+
+/// Parallel [`merge_sort_by_key`] on `2^t_depth` threads.
+pub fn merge_p_sort_by_key<T, K, F>(data: &mut [T], t_depth: u32, f: F)
+where T: Send, K: PartialOrd, F: Fn(&T) -> K + Sync {
+    merge_p_sort_impl(data, t_depth, &|a: &T, b: &T| f(a) < f(b));
+}
+//}}}
+//}}}
@@ -0,0 +1,26 @@
+//! Divide and conquer sorts: merge sort, tim sort, quick sort, intro sort and
+//! pattern-defeating quick sort, and selection of the `k`-th element.
+//!
+//! Merge sort and quick sort have parallel variants, which take a thread
+//! depth `t_depth` and fork that many levels of recursion onto threads.
+mod insertion;
+mod merge;
+mod quick;
+mod intro;
+mod pdq;
+mod tim;
+mod select;
+pub use merge::{merge_sort, merge_sort_by, merge_sort_by_key};
+pub use merge::{merge_p_sort, merge_p_sort_by, merge_p_sort_by_key};
+pub use quick::{quick_sort, quick_sort_by, quick_sort_by_key};
+pub use quick::{quick_p_sort, quick_p_sort_by, quick_p_sort_by_key};
+pub use intro::{intro_sort, intro_sort_by, intro_sort_by_key};
+pub use pdq::{pdq_sort, pdq_sort_by, pdq_sort_by_key};
+pub use tim::{tim_sort, tim_sort_by, tim_sort_by_key};
+pub use select::{select_nth, select_nth_by, select_nth_by_key};
+pub(crate) use insertion::insertion_sort_impl;
+pub(crate) use quick::quick_sort_impl;
+pub(crate) use intro::intro_sort_impl;
+pub(crate) use pdq::pdq_sort_impl;
+pub(crate) use merge::merge_sort_impl;
+pub(crate) use tim::tim_sort_impl;
@@ -0,0 +1,376 @@
+//{{{ Pattern-defeating quick sort
+use std::cmp::Ordering;
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+use crate::heap::heap_sort_impl;
+use crate::hole::Hole;
+use super::insertion::insertion_sort_impl;
+
+// Slices up to this length are sorted with insertion sort.
+const MAX_INSERTION: usize = 20;
+// Slices of at least this length take the pivot as median of three medians.
+const SHORTEST_MEDIAN_OF_MEDIANS: usize = 50;
+// More swaps than this while choosing the pivot mean the slice is likely
+// descending, so it gets reversed.
+const MAX_SWAPS: usize = 4 * 3;
+// Number of out-of-order pairs `partial_insertion_sort` fixes before giving up.
+const MAX_STEPS: usize = 5;
+// Slices shorter than this are not worth shifting in `partial_insertion_sort`.
+const SHORTEST_SHIFTING: usize = 50;
+// Number of elements classified at once by `partition_in_blocks`.
+const BLOCK: usize = 128;
+
+//{{{ Shifting
+// Moves the last element to the left until it meets a smaller one.
+fn shift_tail<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    if n < 2 || !is_less(&data[n - 1], &data[n - 2]) { return; }
+    let mut hole = Hole::new(data, n - 1);
+    hole.move_to(n - 2);
+    while hole.pos() > 0 && is_less(hole.element(), hole.get(hole.pos() - 1)) {
+        hole.move_to(hole.pos() - 1);
+    }
+}
+
+// Moves the first element to the right until it meets a greater one.
+fn shift_head<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    if n < 2 || !is_less(&data[1], &data[0]) { return; }
+    let mut hole = Hole::new(data, 0);
+    hole.move_to(1);
+    while hole.pos() + 1 < n && is_less(hole.get(hole.pos() + 1), hole.element()) {
+        hole.move_to(hole.pos() + 1);
+    }
+}
+
+// Sorts `data` if it has only a few out-of-order elements, and returns
+// whether it has succeeded.
+fn partial_insertion_sort<T, F>(data: &mut [T], is_less: &mut F) -> bool
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    let mut ind = 1;
+    for _ in 0 .. MAX_STEPS {
+        while ind < n && !is_less(&data[ind], &data[ind - 1]) { ind += 1; }
+        if ind == n { return true; }
+        if n < SHORTEST_SHIFTING { return false; }
+        data.swap(ind - 1, ind);
+        shift_tail(&mut data[.. ind], is_less);
+        shift_head(&mut data[ind ..], is_less);
+    }
+    false
+}
+//}}}
+//{{{ Partitioning
+// Moves the elements less than `pivot` to the front of `data` and returns
+// their number.
+//
+// The elements are classified a block at a time from both ends, recording
+// the offsets of the misplaced ones without branching on the comparison, and
+// the misplaced elements of both blocks are then exchanged as one cyclic
+// permutation. No comparison runs while an element is out of the slice.
+fn partition_in_blocks<T, F>(data: &mut [T], pivot: &T, is_less: &mut F) -> usize
+where F: FnMut(&T, &T) -> bool {
+    fn width<T>(l: *const T, r: *const T) -> usize {
+        (r as usize - l as usize) / mem::size_of::<T>()
+    }
+
+    let mut l = data.as_mut_ptr();
+    let mut block_l = BLOCK;
+    let mut start_l: *mut u8 = ptr::null_mut();
+    let mut end_l: *mut u8 = ptr::null_mut();
+    let mut offsets_l = [MaybeUninit::<u8>::uninit(); BLOCK];
+
+    let mut r = unsafe { l.add(data.len()) };
+    let mut block_r = BLOCK;
+    let mut start_r: *mut u8 = ptr::null_mut();
+    let mut end_r: *mut u8 = ptr::null_mut();
+    let mut offsets_r = [MaybeUninit::<u8>::uninit(); BLOCK];
+
+    loop {
+        let is_done = width(l, r) <= 2 * BLOCK;
+        if is_done {
+            // Shrink the blocks so that they exactly cover the rest.
+            let mut rem = width(l, r);
+            if start_l < end_l || start_r < end_r {
+                rem -= BLOCK;
+            }
+            if start_l < end_l {
+                block_r = rem;
+            } else if start_r < end_r {
+                block_l = rem;
+            } else {
+                block_l = rem / 2;
+                block_r = rem - block_l;
+            }
+        }
+
+        if start_l == end_l {
+            start_l = offsets_l.as_mut_ptr() as *mut u8;
+            end_l = start_l;
+            let mut elem = l;
+            for i in 0 .. block_l {
+                unsafe {
+                    *end_l = i as u8;
+                    end_l = end_l.add(!is_less(&*elem, pivot) as usize);
+                    elem = elem.add(1);
+                }
+            }
+        }
+        if start_r == end_r {
+            start_r = offsets_r.as_mut_ptr() as *mut u8;
+            end_r = start_r;
+            let mut elem = r;
+            for i in 0 .. block_r {
+                unsafe {
+                    elem = elem.sub(1);
+                    *end_r = i as u8;
+                    end_r = end_r.add(is_less(&*elem, pivot) as usize);
+                }
+            }
+        }
+
+        let count = width(start_l, end_l).min(width(start_r, end_r));
+        if count > 0 {
+            unsafe {
+                let left = |start_l: *mut u8| l.add(*start_l as usize);
+                let right = |start_r: *mut u8| r.sub(*start_r as usize + 1);
+                let tmp = ptr::read(left(start_l));
+                ptr::copy_nonoverlapping(right(start_r), left(start_l), 1);
+                for _ in 1 .. count {
+                    start_l = start_l.add(1);
+                    ptr::copy_nonoverlapping(left(start_l), right(start_r), 1);
+                    start_r = start_r.add(1);
+                    ptr::copy_nonoverlapping(right(start_r), left(start_l), 1);
+                }
+                ptr::write(right(start_r), tmp);
+                start_l = start_l.add(1);
+                start_r = start_r.add(1);
+            }
+        }
+
+        if start_l == end_l {
+            l = unsafe { l.add(block_l) };
+        }
+        if start_r == end_r {
+            r = unsafe { r.sub(block_r) };
+        }
+        if is_done { break; }
+    }
+
+    // One block may still hold misplaced elements; move them to its far end.
+    if start_l < end_l {
+        while start_l < end_l {
+            unsafe {
+                end_l = end_l.sub(1);
+                ptr::swap(l.add(*end_l as usize), r.sub(1));
+                r = r.sub(1);
+            }
+        }
+        width(data.as_ptr(), r)
+    } else if start_r < end_r {
+        while start_r < end_r {
+            unsafe {
+                end_r = end_r.sub(1);
+                ptr::swap(l, r.sub(*end_r as usize + 1));
+                l = l.add(1);
+            }
+        }
+        width(data.as_ptr(), l)
+    } else {
+        width(data.as_ptr(), l)
+    }
+}
+
+// Partitions `data` around `data[pivot]` into elements less than it and
+// elements not less than it. Returns the final index of the pivot and whether
+// `data` was already partitioned.
+fn partition<T, F>(data: &mut [T], pivot: usize, is_less: &mut F) -> (usize, bool)
+where F: FnMut(&T, &T) -> bool {
+    data.swap(0, pivot);
+    let (ind, was_partitioned) = {
+        let (pivot, data) = data.split_at_mut(1);
+        let pivot = &pivot[0];
+        let (mut ind_l, mut ind_r) = (0, data.len());
+        while ind_l < ind_r && is_less(&data[ind_l], pivot) { ind_l += 1; }
+        while ind_l < ind_r && !is_less(&data[ind_r - 1], pivot) { ind_r -= 1; }
+        (ind_l + partition_in_blocks(&mut data[ind_l .. ind_r], pivot, is_less),
+         ind_l >= ind_r)
+    };
+    data.swap(0, ind);
+    (ind, was_partitioned)
+}
+
+// Partitions `data` around `data[pivot]`, which no element is less than, into
+// elements equal to it and greater than it. Returns the number of the former.
+fn partition_equal<T, F>(data: &mut [T], pivot: usize, is_less: &mut F) -> usize
+where F: FnMut(&T, &T) -> bool {
+    data.swap(0, pivot);
+    let (pivot, data) = data.split_at_mut(1);
+    let pivot = &pivot[0];
+    let (mut ind_l, mut ind_r) = (0, data.len());
+    loop {
+        while ind_l < ind_r && !is_less(pivot, &data[ind_l]) { ind_l += 1; }
+        while ind_l < ind_r && is_less(pivot, &data[ind_r - 1]) { ind_r -= 1; }
+        if ind_l >= ind_r { break; }
+        ind_r -= 1;
+        data.swap(ind_l, ind_r);
+        ind_l += 1;
+    }
+    ind_l + 1
+}
+//}}}
+//{{{ Pivot selection
+// Scatters a few elements around the middle, breaking the patterns which made
+// the previous partitions unbalanced.
+fn break_patterns<T>(data: &mut [T]) {
+    let n = data.len();
+    if n < 8 { return; }
+    let mut random = n as u32;
+    let mut gen_u32 = || {
+        random ^= random << 13;
+        random ^= random >> 17;
+        random ^= random << 5;
+        random
+    };
+    let mut gen_usize = || if usize::BITS <= 32 {
+        gen_u32() as usize
+    } else {
+        ((gen_u32() as u64) << 32 | gen_u32() as u64) as usize
+    };
+    let modulus = n.next_power_of_two();
+    let pos = n / 4 * 2;
+    for i in 0 .. 3 {
+        let mut other = gen_usize() & (modulus - 1);
+        if other >= n { other -= n; }
+        data.swap(pos - 1 + i, other);
+    }
+}
+
+// Chooses a pivot by median of three (of three medians for long slices) and
+// returns its index, together with whether `data` is likely sorted already.
+// A slice which looks descending gets reversed first.
+fn choose_pivot<T, F>(data: &mut [T], is_less: &mut F) -> (usize, bool)
+where F: FnMut(&T, &T) -> bool {
+    fn sort2<T, F>(data: &[T], a: &mut usize, b: &mut usize,
+                   swaps: &mut usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        if is_less(&data[*b], &data[*a]) {
+            mem::swap(a, b);
+            *swaps += 1;
+        }
+    }
+    fn sort3<T, F>(data: &[T], a: &mut usize, b: &mut usize, c: &mut usize,
+                   swaps: &mut usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        sort2(data, a, b, swaps, is_less);
+        sort2(data, b, c, swaps, is_less);
+        sort2(data, a, b, swaps, is_less);
+    }
+    fn sort_adjacent<T, F>(data: &[T], a: &mut usize, swaps: &mut usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        let (mut a0, mut a2) = (*a - 1, *a + 1);
+        sort3(data, &mut a0, a, &mut a2, swaps, is_less);
+    }
+
+    let n = data.len();
+    let (mut a, mut b, mut c) = (n / 4, n / 4 * 2, n / 4 * 3);
+    let mut swaps = 0;
+    if n >= 8 {
+        if n >= SHORTEST_MEDIAN_OF_MEDIANS {
+            sort_adjacent(data, &mut a, &mut swaps, is_less);
+            sort_adjacent(data, &mut b, &mut swaps, is_less);
+            sort_adjacent(data, &mut c, &mut swaps, is_less);
+        }
+        sort3(data, &mut a, &mut b, &mut c, &mut swaps, is_less);
+    }
+    if swaps < MAX_SWAPS {
+        (b, swaps == 0)
+    } else {
+        data.reverse();
+        (n - 1 - b, true)
+    }
+}
+//}}}
+pub(crate) fn pdq_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    // `pred` is the pivot of the partition on the left of `data`, if any.
+    fn pdq_sort<'a, T, F>(mut data: &'a mut [T], mut pred: Option<&'a T>,
+                          mut limit: u32, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        let mut was_balanced = true;
+        let mut was_partitioned = true;
+        loop {
+            let n = data.len();
+            if n <= MAX_INSERTION {
+                insertion_sort_impl(data, is_less);
+                return;
+            }
+            if limit == 0 {
+                heap_sort_impl(data, is_less);
+                return;
+            }
+            if !was_balanced {
+                break_patterns(data);
+                limit -= 1;
+            }
+            let (pivot, likely_sorted) = choose_pivot(data, is_less);
+            if was_balanced && was_partitioned && likely_sorted
+                && partial_insertion_sort(data, is_less) {
+                return;
+            }
+            // Nothing on the left is greater than the pivot of the predecessor,
+            // so a pivot equal to it starts a run of equal elements.
+            if let Some(p) = pred {
+                if !is_less(p, &data[pivot]) {
+                    let ind = partition_equal(data, pivot, is_less);
+                    data = &mut mem::take(&mut data)[ind ..];
+                    continue;
+                }
+            }
+            let (ind, was_p) = partition(data, pivot, is_less);
+            was_balanced = ind.min(n - ind) >= n / 8;
+            was_partitioned = was_p;
+            let (data1, data2) = mem::take(&mut data).split_at_mut(ind);
+            let (pivot, data2) = data2.split_at_mut(1);
+            let pivot = &pivot[0];
+            if data1.len() < data2.len() {
+                pdq_sort(data1, pred, limit, is_less);
+                data = data2;
+                pred = Some(pivot);
+            } else {
+                pdq_sort(data2, Some(pivot), limit, is_less);
+                data = data1;
+            }
+        }
+    }
+    if mem::size_of::<T>() == 0 { return; }
+    let limit = usize::BITS - data.len().leading_zeros();
+    pdq_sort(data, None, limit, is_less);
+}
+
+/// Sorts `data` with pattern-defeating quick sort.
+///
+/// Partitions without branch mispredictions by classifying blocks of
+/// elements, finishes already sorted slices in linear time, breaks the
+/// patterns which cause unbalanced partitions, and falls back to heap sort if
+/// they keep coming, so it runs in `O(n log n)` in the worst case.
+pub fn pdq_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    pdq_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with pattern-defeating quick sort according to `compare`.
+pub fn pdq_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    pdq_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with pattern-defeating quick sort by the key extracted with
+/// `f`.
+pub fn pdq_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    pdq_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
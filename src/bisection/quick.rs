@@ -0,0 +1,153 @@
+//{{{ Quick sort
+//{{{ Quick sort, serial
+use std::cmp::Ordering;
+use crate::small::{SMALL_MAX, sort_small_impl};
+/// Partitions non-empty `data` around the pivot `data[0]` and returns the
+/// final position of the pivot, with no greater element before it and no
+/// less element after it.
+pub(crate) fn partition<T, F>(data: &mut [T], is_less: &mut F) -> usize
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    let (mut ind_l, mut ind_r) = (1, n - 1);
+    loop {
+        while ind_l < n && !is_less(&data[0], &data[ind_l]) { ind_l += 1; }
+        while ind_l < ind_r && !is_less(&data[ind_r], &data[0]) { ind_r -= 1; }
+        if ind_l >= ind_r { break; }
+        data.swap(ind_l, ind_r);
+    }
+    data.swap(0, ind_l - 1);
+    ind_l - 1
+}
+
+pub(crate) fn quick_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    if data.len() <= SMALL_MAX {
+        sort_small_impl(data, is_less);
+        return;
+    }
+    let mid = partition(data, is_less);
+    quick_sort_impl(&mut data[.. mid], is_less);
+    quick_sort_impl(&mut data[mid + 1 ..], is_less);
+}
+
+/// Sorts `data` with quick sort, taking the first element as pivot. Slices
+/// up to [`SMALL_MAX`] elements are sorted with a fixed network.
+pub fn quick_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    quick_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with quick sort according to `compare`.
+pub fn quick_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    quick_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with quick sort by the key extracted with `f`.
+pub fn quick_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    quick_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
+//{{{ Quick sort, parallel
+use crate::pool;
+use super::intro::{choose_pivot, intro_sort_impl};
+
+// Slices up to this length are partitioned and sorted on a single thread.
+const SEQUENTIAL_THRESHOLD: usize = 1 << 12;
+
+// Swaps `data1` and `data2` of equal length, on `2^t_depth` threads.
+fn swap_p<T>(data1: &mut [T], data2: &mut [T], t_depth: u32)
+where T: Send {
+    if t_depth == 0 || data1.len() <= SEQUENTIAL_THRESHOLD {
+        data1.swap_with_slice(data2);
+        return;
+    }
+    let mid = data1.len() / 2;
+    let (data11, data12) = data1.split_at_mut(mid);
+    let (data21, data22) = data2.split_at_mut(mid);
+    pool::join(|| swap_p(data11, data21, t_depth - 1),
+               || swap_p(data12, data22, t_depth - 1));
+}
+
+// Moves the elements of `data` which satisfy `pred` to the front and returns
+// their count. The slice is cut into `2^t_depth` blocks partitioned in
+// parallel; the halves are then joined by swapping the elements of the first
+// half which fail `pred` with the ones of the second half which satisfy it.
+fn partition_p<T, P>(data: &mut [T], t_depth: u32, pred: &P) -> usize
+where T: Send, P: Fn(&T) -> bool + Sync {
+    let n = data.len();
+    if t_depth == 0 || n <= SEQUENTIAL_THRESHOLD {
+        let (mut ind_l, mut ind_r) = (0, n);
+        loop {
+            while ind_l < ind_r && pred(&data[ind_l]) { ind_l += 1; }
+            while ind_l < ind_r && !pred(&data[ind_r - 1]) { ind_r -= 1; }
+            if ind_l >= ind_r { break; }
+            data.swap(ind_l, ind_r - 1);
+            (ind_l, ind_r) = (ind_l + 1, ind_r - 1);
+        }
+        return ind_l;
+    }
+    let mid = n / 2;
+    let (data1, data2) = data.split_at_mut(mid);
+    let (mid1, mid2) = pool::join(|| partition_p(data1, t_depth - 1, pred),
+                                  || partition_p(data2, t_depth - 1, pred));
+    // `data[mid1 .. mid]` fails `pred`, `data[mid .. mid + mid2]` satisfies
+    // it: swapping the shorter one with the far end of the other one puts
+    // both in order.
+    let count = (mid - mid1).min(mid2);
+    let (head, rest) = data[mid1 .. mid + mid2].split_at_mut(count);
+    let len = rest.len();
+    swap_p(head, &mut rest[len - count ..], t_depth);
+    mid1 + mid2
+}
+
+fn quick_p_sort_impl<T, F>(data: &mut [T], t_depth: u32, is_less: &F)
+where T: Send + Sync, F: Fn(&T, &T) -> bool + Sync {
+    let n = data.len();
+    if t_depth == 0 || n <= SEQUENTIAL_THRESHOLD {
+        intro_sort_impl(data, &mut &*is_less);
+        return;
+    }
+    choose_pivot(data, &mut &*is_less);
+    let (pivot, rest) = data.split_first_mut().unwrap();
+    // Three-way partition: less than the pivot, equal to it, greater than
+    // it. The elements equal to the pivot are done, so that runs of equal
+    // elements cannot unbalance the recursion.
+    let mid = partition_p(rest, t_depth, &|x: &T| !is_less(pivot, x));
+    let less = partition_p(&mut rest[.. mid], t_depth, &|x: &T| is_less(x, pivot));
+    data.swap(0, less);
+    let (data1, data2) = data.split_at_mut(less);
+    pool::join(|| quick_p_sort_impl(data1, t_depth - 1, is_less),
+               || quick_p_sort_impl(&mut data2[mid - less + 1 ..], t_depth - 1, is_less));
+}
+
+/// Sorts `data` with quick sort, forking the first `t_depth` levels of
+/// recursion onto separate threads.
+///
+/// On these levels the pivot is the median of three (or of nine) elements,
+/// and the partition is parallel as well: blocks of the slice are
+/// partitioned on `2^t_depth` threads, and the misplaced parts are swapped
+/// into place in parallel. The elements equal to the pivot are split off, so
+/// that runs of equal elements do not unbalance the recursion. Slices below
+/// a few thousand elements stay on one thread with
+/// [`intro_sort`](super::intro_sort), so the worst case is `O(n log n)`. In
+/// place, without any scratch buffer.
+pub fn quick_p_sort<T>(data: &mut [T], t_depth: u32)
+where T: PartialOrd + Send + Sync {
+    quick_p_sort_impl(data, t_depth, &T::lt);
+}
+
+/// Parallel [`quick_sort_by`] on `2^t_depth` threads.
+pub fn quick_p_sort_by<T, F>(data: &mut [T], t_depth: u32, compare: F)
+where T: Send + Sync, F: Fn(&T, &T) -> Ordering + Sync {
+    quick_p_sort_impl(data, t_depth, &|a: &T, b: &T| compare(a, b) == Ordering::Less);
+}
+
+/// Parallel [`quick_sort_by_key`] on `2^t_depth` threads.
+pub fn quick_p_sort_by_key<T, K, F>(data: &mut [T], t_depth: u32, f: F)
+where T: Send + Sync, K: PartialOrd, F: Fn(&T) -> K + Sync {
+    quick_p_sort_impl(data, t_depth, &|a: &T, b: &T| f(a) < f(b));
+}
+//}}}
+//}}}
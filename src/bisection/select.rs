@@ -0,0 +1,111 @@
+//{{{ Selection
+use std::cmp::Ordering;
+use super::insertion::insertion_sort_impl;
+use super::intro::choose_pivot;
+use super::quick::partition;
+
+const INSERTION_THRESHOLD: usize = 16;
+
+// Moves the elements of `data[.. pivot]` less than `data[pivot]` to the front
+// and returns their number. The elements left between them and the pivot
+// are all equal to it.
+fn partition_less<T, F>(data: &mut [T], pivot: usize, is_less: &mut F) -> usize
+where F: FnMut(&T, &T) -> bool {
+    let (rest, pivot) = data.split_at_mut(pivot);
+    let mut mid = 0;
+    for ind in 0 .. rest.len() {
+        if is_less(&rest[ind], &pivot[0]) {
+            rest.swap(ind, mid);
+            mid += 1;
+        }
+    }
+    mid
+}
+
+// Moves the median of the medians of groups of five to the front. At least
+// 3/10 of the elements are no greater and 3/10 no less than it.
+fn median_of_medians<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let m = data.len() / 5;
+    for ind in 0 .. m {
+        insertion_sort_impl(&mut data[5 * ind .. 5 * ind + 5], is_less);
+        data.swap(ind, 5 * ind + 2);
+    }
+    select_nth_impl(&mut data[.. m], m / 2, is_less);
+    data.swap(0, m / 2);
+}
+
+fn select_nth_impl<T, F>(mut data: &mut [T], mut k: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    // Every `STEPS` partitions must halve the slice, or the median of medians
+    // takes over for good: the work is then bounded by a geometric series,
+    // which keeps the worst case linear.
+    const STEPS: u32 = 3;
+    let (mut checkpoint, mut steps) = (data.len(), 0);
+    let mut fallback = false;
+    loop {
+        let n = data.len();
+        if n <= INSERTION_THRESHOLD {
+            insertion_sort_impl(data, is_less);
+            return;
+        }
+        if !fallback && steps == STEPS {
+            fallback = n > checkpoint / 2;
+            (checkpoint, steps) = (n, 0);
+        }
+        if fallback {
+            median_of_medians(data, is_less);
+        } else {
+            choose_pivot(data, is_less);
+            steps += 1;
+        }
+        let mid = partition(data, is_less);
+        if k > mid {
+            data = &mut data[mid + 1 ..];
+            k -= mid + 1;
+        } else if k < mid {
+            // Every element equal to the pivot ended up before it, so split
+            // them off to keep runs of equal elements from stalling.
+            let mid = partition_less(data, mid, is_less);
+            if k >= mid { return; }
+            data = &mut data[.. mid];
+        } else { return; }
+    }
+}
+
+/// Reorders `data` so that `data[k]` is the element which would be there if
+/// `data` were sorted, with no greater element before it and no less element
+/// after it.
+///
+/// Runs quickselect on the partition loop of quick sort and falls back to the
+/// median of medians pivot as soon as three partitions in a row fail to halve
+/// the slice (introselect), so that the worst case is `O(n)`.
+///
+/// # Panics
+/// Panics if `k >= data.len()`.
+pub fn select_nth<T>(data: &mut [T], k: usize)
+where T: PartialOrd {
+    assert!(k < data.len());
+    select_nth_impl(data, k, &mut T::lt);
+}
+
+/// Reorders `data` around its `k`-th element according to `compare`.
+///
+/// # Panics
+/// Panics if `k >= data.len()`.
+pub fn select_nth_by<T, F>(data: &mut [T], k: usize, mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    assert!(k < data.len());
+    select_nth_impl(data, k, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Reorders `data` around its `k`-th element by the key extracted with `f`.
+///
+/// # Panics
+/// Panics if `k >= data.len()`.
+pub fn select_nth_by_key<T, K, F>(data: &mut [T], k: usize, mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    assert!(k < data.len());
+    select_nth_impl(data, k, &mut |a, b| f(a) < f(b));
+}
+//}}}
@@ -0,0 +1,305 @@
+//{{{ Tim sort
+use std::cmp::Ordering;
+use std::ptr;
+
+// Galloping starts after this many consecutive wins of one run.
+const MIN_GALLOP: usize = 7;
+
+//{{{ Runs
+// Shortest run worth merging for a slice of length `n`: between 32 and 64,
+// so that `n / min_run` is a power of two or slightly below one.
+fn min_run(mut n: usize) -> usize {
+    let mut r = 0;
+    while n >= 64 {
+        r |= n & 1;
+        n >>= 1;
+    }
+    n + r
+}
+
+// Returns the length of the run at the front of `data`, reversing it first if
+// it is strictly descending.
+fn count_run<T, F>(data: &mut [T], is_less: &mut F) -> usize
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    if n < 2 { return n; }
+    let mut end = 2;
+    if is_less(&data[1], &data[0]) {
+        while end < n && is_less(&data[end], &data[end - 1]) { end += 1; }
+        data[.. end].reverse();
+    } else {
+        while end < n && !is_less(&data[end], &data[end - 1]) { end += 1; }
+    }
+    end
+}
+
+// Extends the sorted prefix `data[.. sorted]` to all of `data`, inserting
+// each element after the equal ones found by binary search.
+fn binary_insertion_sort<T, F>(data: &mut [T], sorted: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    for ind in sorted.max(1) .. data.len() {
+        let (head, tail) = data.split_at(ind);
+        let pos = head.partition_point(|x| !is_less(&tail[0], x));
+        data[pos ..= ind].rotate_right(1);
+    }
+}
+
+// Index of the run which should be merged with its successor to restore
+// the invariants of the run stack, if any. The last run is merged down
+// unconditionally once it reaches the end of the slice.
+fn collapse(runs: &[(usize, usize)], n: usize) -> Option<usize> {
+    let k = runs.len();
+    let len = |i: usize| runs[i].1;
+    if k >= 2 && (runs[k - 1].0 + len(k - 1) == n
+        || len(k - 2) <= len(k - 1)
+        || k >= 3 && len(k - 3) <= len(k - 2) + len(k - 1)
+        || k >= 4 && len(k - 4) <= len(k - 3) + len(k - 2)) {
+        if k >= 3 && len(k - 3) < len(k - 1) { Some(k - 3) } else { Some(k - 2) }
+    } else { None }
+}
+//}}}
+//{{{ Galloping
+// Index of the first element of `data` satisfying `pred`, which must hold for
+// a suffix of `data`. Probes at exponentially growing distance from the front
+// before the binary search, so that short prefixes are found quickly.
+fn gallop_front<T>(data: &[T], mut pred: impl FnMut(&T) -> bool) -> usize {
+    let n = data.len();
+    let (mut last, mut ofs) = (0, 1);
+    while ofs <= n && !pred(&data[ofs - 1]) {
+        last = ofs;
+        ofs = ofs * 2 + 1;
+    }
+    let end = ofs.min(n);
+    last + data[last .. end].partition_point(|x| !pred(x))
+}
+
+// Same as `gallop_front`, probing from the back instead.
+fn gallop_back<T>(data: &[T], mut pred: impl FnMut(&T) -> bool) -> usize {
+    let n = data.len();
+    let (mut last, mut ofs) = (0, 1);
+    while ofs <= n && pred(&data[n - ofs]) {
+        last = ofs;
+        ofs = ofs * 2 + 1;
+    }
+    let start = n - ofs.min(n);
+    start + data[start .. n - last].partition_point(|x| !pred(x))
+}
+//}}}
+//{{{ Merging
+// `len` elements waiting at `src` to be moved into the gap at `dest`. Dropping
+// it fills the gap, so each element is in the slice exactly once even if
+// `is_less` panics in the middle of a merge.
+struct MergeHole<T> {
+    src  : *const T,
+    dest : *mut T,
+    len  : usize,
+}
+
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        unsafe { ptr::copy_nonoverlapping(self.src, self.dest, self.len); }
+    }
+}
+
+// Merges `data[.. mid]` and `data[mid ..]` front to back, moving the first
+// run out to `buf`. Requires `data[mid] < data[0]` and
+// `data[n - 1] < data[mid - 1]`, so that neither run ends the merge at once.
+fn merge_lo<T, F>(data: &mut [T], mid: usize, buf: &mut Vec<T>,
+                  min_gallop: &mut usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    let v = data.as_mut_ptr();
+    let b = buf.as_mut_ptr();
+    unsafe {
+        ptr::copy_nonoverlapping(v, b, mid);
+        // The first run waits in `hole.src[.. hole.len]`, the second one in
+        // `v[ind2 ..]`, and `hole.dest + hole.len == v + ind2`.
+        let mut hole = MergeHole { src: b, dest: v, len: mid };
+        let mut ind2 = mid;
+        'out: loop {
+            let (mut count1, mut count2) = (0, 0);
+            while count1 < *min_gallop && count2 < *min_gallop {
+                if is_less(&*v.add(ind2), &*hole.src) {
+                    ptr::copy_nonoverlapping(v.add(ind2), hole.dest, 1);
+                    hole.dest = hole.dest.add(1);
+                    ind2 += 1;
+                    if ind2 == n { break 'out; }
+                    (count1, count2) = (0, count2 + 1);
+                } else {
+                    ptr::copy_nonoverlapping(hole.src, hole.dest, 1);
+                    (hole.src, hole.dest, hole.len) = (hole.src.add(1), hole.dest.add(1), hole.len - 1);
+                    if hole.len == 0 { break 'out; }
+                    (count1, count2) = (count1 + 1, 0);
+                }
+            }
+            loop {
+                let run1 = std::slice::from_raw_parts(hole.src, hole.len);
+                let key = &*v.add(ind2);
+                let count1 = gallop_front(run1, |x| is_less(key, x));
+                ptr::copy_nonoverlapping(hole.src, hole.dest, count1);
+                (hole.src, hole.dest, hole.len) =
+                    (hole.src.add(count1), hole.dest.add(count1), hole.len - count1);
+                if hole.len == 0 { break 'out; }
+                ptr::copy_nonoverlapping(v.add(ind2), hole.dest, 1);
+                hole.dest = hole.dest.add(1);
+                ind2 += 1;
+                if ind2 == n { break 'out; }
+
+                let run2 = std::slice::from_raw_parts(v.add(ind2), n - ind2);
+                let key = &*hole.src;
+                let count2 = gallop_front(run2, |x| !is_less(x, key));
+                ptr::copy(v.add(ind2), hole.dest, count2);
+                hole.dest = hole.dest.add(count2);
+                ind2 += count2;
+                if ind2 == n { break 'out; }
+                ptr::copy_nonoverlapping(hole.src, hole.dest, 1);
+                (hole.src, hole.dest, hole.len) = (hole.src.add(1), hole.dest.add(1), hole.len - 1);
+                if hole.len == 0 { break 'out; }
+
+                if count1 < MIN_GALLOP && count2 < MIN_GALLOP { break; }
+                *min_gallop = min_gallop.saturating_sub(1);
+            }
+            *min_gallop += 2;
+        }
+    }
+}
+
+// Merges `data[.. mid]` and `data[mid ..]` back to front, moving the second
+// run out to `buf`. Same requirements as `merge_lo`.
+fn merge_hi<T, F>(data: &mut [T], mid: usize, buf: &mut Vec<T>,
+                  min_gallop: &mut usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    let v = data.as_mut_ptr();
+    let b = buf.as_mut_ptr();
+    unsafe {
+        ptr::copy_nonoverlapping(v.add(mid), b, n - mid);
+        // The first run waits in `v[.. ind1]`, the second one in
+        // `b[.. hole.len]`, and `hole.dest == v + ind1`.
+        let mut hole = MergeHole { src: b, dest: v.add(mid), len: n - mid };
+        let mut ind1 = mid;
+        'out: loop {
+            let (mut count1, mut count2) = (0, 0);
+            while count1 < *min_gallop && count2 < *min_gallop {
+                if is_less(&*b.add(hole.len - 1), &*v.add(ind1 - 1)) {
+                    ptr::copy_nonoverlapping(v.add(ind1 - 1), v.add(ind1 + hole.len - 1), 1);
+                    ind1 -= 1;
+                    hole.dest = v.add(ind1);
+                    if ind1 == 0 { break 'out; }
+                    (count1, count2) = (count1 + 1, 0);
+                } else {
+                    ptr::copy_nonoverlapping(b.add(hole.len - 1), v.add(ind1 + hole.len - 1), 1);
+                    hole.len -= 1;
+                    if hole.len == 0 { break 'out; }
+                    (count1, count2) = (0, count2 + 1);
+                }
+            }
+            loop {
+                let run1 = std::slice::from_raw_parts(v, ind1);
+                let key = &*b.add(hole.len - 1);
+                let pos = gallop_back(run1, |x| is_less(key, x));
+                let count1 = ind1 - pos;
+                ptr::copy(v.add(pos), v.add(pos + hole.len), count1);
+                ind1 = pos;
+                hole.dest = v.add(ind1);
+                if ind1 == 0 { break 'out; }
+                ptr::copy_nonoverlapping(b.add(hole.len - 1), v.add(ind1 + hole.len - 1), 1);
+                hole.len -= 1;
+                if hole.len == 0 { break 'out; }
+
+                let run2 = std::slice::from_raw_parts(b, hole.len);
+                let key = &*v.add(ind1 - 1);
+                let pos = gallop_back(run2, |x| !is_less(x, key));
+                let count2 = hole.len - pos;
+                ptr::copy_nonoverlapping(b.add(pos), v.add(ind1 + pos), count2);
+                hole.len = pos;
+                if hole.len == 0 { break 'out; }
+                ptr::copy_nonoverlapping(v.add(ind1 - 1), v.add(ind1 + hole.len - 1), 1);
+                ind1 -= 1;
+                hole.dest = v.add(ind1);
+                if ind1 == 0 { break 'out; }
+
+                if count1 < MIN_GALLOP && count2 < MIN_GALLOP { break; }
+                *min_gallop = min_gallop.saturating_sub(1);
+            }
+            *min_gallop += 2;
+        }
+    }
+}
+
+// Merges the sorted runs `data[.. mid]` and `data[mid ..]`, after skipping
+// the elements already in place at both ends.
+fn merge_runs<T, F>(data: &mut [T], mid: usize, buf: &mut Vec<T>,
+                    min_gallop: &mut usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let start = {
+        let (run1, run2) = data.split_at(mid);
+        gallop_front(run1, |x| is_less(&run2[0], x))
+    };
+    if start == mid { return; }
+    let data = &mut data[start ..];
+    let mid = mid - start;
+    let end = {
+        let (run1, run2) = data.split_at(mid);
+        gallop_back(run2, |x| !is_less(x, &run1[mid - 1]))
+    };
+    let data = &mut data[.. mid + end];
+    if mid <= end {
+        merge_lo(data, mid, buf, min_gallop, is_less);
+    } else {
+        merge_hi(data, mid, buf, min_gallop, is_less);
+    }
+}
+//}}}
+pub(crate) fn tim_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    if n < 2 { return; }
+    let min_run = min_run(n);
+    let mut buf = Vec::with_capacity(n / 2);
+    let mut min_gallop = MIN_GALLOP;
+    let mut runs: Vec<(usize, usize)> = vec![];
+    let mut start = 0;
+    while start < n {
+        let mut len = count_run(&mut data[start ..], is_less);
+        if len < min_run {
+            let end = n.min(start + min_run);
+            binary_insertion_sort(&mut data[start .. end], len, is_less);
+            len = end - start;
+        }
+        runs.push((start, len));
+        start += len;
+        while let Some(r) = collapse(&runs, n) {
+            let (start1, len1) = runs[r];
+            let (_, len2) = runs.remove(r + 1);
+            merge_runs(&mut data[start1 .. start1 + len1 + len2], len1,
+                       &mut buf, &mut min_gallop, is_less);
+            runs[r].1 += len2;
+        }
+    }
+}
+
+/// Sorts `data` with tim sort, a stable natural merge sort.
+///
+/// Ascending and strictly descending runs already present in `data` are
+/// taken as they are, short ones are extended by binary insertion sort, and
+/// runs are merged with galloping when one of them keeps winning. All merges
+/// share a scratch buffer of `n / 2` elements. Runs in `O(n)` on input made
+/// of a few runs and `O(n log n)` in the worst case.
+pub fn tim_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    tim_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with tim sort according to `compare`.
+pub fn tim_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    tim_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with tim sort by the key extracted with `f`.
+pub fn tim_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    tim_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
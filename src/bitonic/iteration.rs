@@ -0,0 +1,342 @@
+//{{{ Iterator `SortIndex`
+use std::cmp::Ordering;
+use std::mem;
+pub(super) struct SortIndex {
+    start          : usize,
+    end            : usize,
+    bound          : usize,
+    char_major_bit : u32,
+    char_minor_bit : u32,
+    char_major     : usize,
+    char_minor     : usize,
+    rev            : bool,
+}
+
+impl SortIndex {
+    pub(super) fn new(start0: usize, end: usize, bound: usize,
+           char_major_bit: u32, char_minor_bit: u32, rev: bool) -> Self {
+        assert!(char_major_bit > char_minor_bit);
+        let char_minor = 1usize << char_minor_bit;
+        let char_major = 1usize << char_major_bit;
+        let mut start = start0;
+        let pair = {
+            let mut rx = start >> char_major_bit;
+            let mut i = 1u32;
+            loop {
+                let rxs = rx.wrapping_shr(i);
+                if rxs == 0 { break; }
+                rx ^= rxs;
+                i = i.wrapping_shl(1);
+            }
+            (rx & 1).wrapping_shl(char_minor_bit)
+        };
+        if start & char_minor ^ pair != 0 {
+            if start & char_minor == 0 {
+                start |= char_minor;
+            } else {
+                start = start.wrapping_add(1);
+                let mut rx = start.wrapping_shr(char_major_bit);
+                let mut i = 1u32;
+                loop {
+                    let rxs = rx.wrapping_shr(i);
+                    if rxs == 0 { break; }
+                    rx ^= rxs;
+                    i = i.wrapping_shl(1);
+                }
+                start = start & !char_minor ^ (rx & 1).wrapping_shl(char_minor_bit);
+            }
+            if start <= start0 { start = end; }
+        }
+        SortIndex {
+            start, end, bound,
+            char_major_bit, char_minor_bit,
+            char_major, char_minor,
+            rev,
+        }
+    }
+}
+
+impl Iterator for SortIndex {
+    type Item = (usize, usize);
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let ret = self.start;
+        let retx = ret ^ self.char_minor;
+        if ret >= self.end || ret >= self.bound || retx >= self.bound {
+            return None;
+        }
+        let pair = ret & self.char_minor;
+        let mut x = (ret | self.char_minor).wrapping_add(1);
+        if (x ^ ret) & self.char_major != 0 {
+            let mut rx = x.wrapping_shr(self.char_major_bit);
+            let mut i = 1u32;
+            loop {
+                let rxs = rx.wrapping_shr(i);
+                if rxs == 0 { break; }
+                rx ^= rxs;
+                i = i.wrapping_shl(1);
+            }
+            x ^= (rx & 1).wrapping_shl(self.char_minor_bit);
+        } else {
+            x = x & !self.char_minor ^ pair;
+        }
+        self.start = if x <= ret { self.end } else { x };
+        Some(if self.rev { (retx, ret) } else { (ret, retx) })
+    }
+}
+//}}}
+//{{{ Trait `Lanes`
+// What the network sorts: a slice, or a slice of keys whose exchanges are
+// applied to a slice of values of the same length as well.
+pub(super) trait Lanes {
+    type Key;
+    fn len(&self) -> usize;
+    fn key(&self, ind: usize) -> &Self::Key;
+    // Swaps the elements `ind1` and `ind2`.
+    fn swap_at(&mut self, ind1: usize, ind2: usize);
+    // Swaps the element `ind` with the element `ind` of `other`.
+    fn swap_with(&mut self, other: &mut Self, ind: usize);
+}
+
+impl<T> Lanes for &mut [T] {
+    type Key = T;
+    fn len(&self) -> usize { <[T]>::len(self) }
+    fn key(&self, ind: usize) -> &T { &self[ind] }
+    fn swap_at(&mut self, ind1: usize, ind2: usize) { <[T]>::swap(self, ind1, ind2); }
+    fn swap_with(&mut self, other: &mut Self, ind: usize) {
+        mem::swap(&mut self[ind], &mut other[ind]);
+    }
+}
+
+impl<K, V> Lanes for (&mut [K], &mut [V]) {
+    type Key = K;
+    fn len(&self) -> usize { self.0.len() }
+    fn key(&self, ind: usize) -> &K { &self.0[ind] }
+    fn swap_at(&mut self, ind1: usize, ind2: usize) {
+        self.0.swap(ind1, ind2);
+        self.1.swap(ind1, ind2);
+    }
+    fn swap_with(&mut self, other: &mut Self, ind: usize) {
+        mem::swap(&mut self.0[ind], &mut other.0[ind]);
+        mem::swap(&mut self.1[ind], &mut other.1[ind]);
+    }
+}
+//}}}
+//{{{ Bitonic sort, iteration
+//{{{ Bitonic sort, iteration, serial
+pub(super) fn bitonic_i_sort_impl<L, F>(data: &mut L, is_less: &mut F)
+where L: Lanes, F: FnMut(&L::Key, &L::Key) -> bool {
+    if data.len() <= 1 { return; }
+    let n = data.len();
+    let depth = {
+        let (mut depth, mut n) = (1u32, n - 1 >> 1);
+        while n != 0 { n >>= 1; depth += 1; }
+        depth
+    };
+    let mut rev = depth & 1 == 0;
+    for cnt in 1 ..= depth {
+        for i in (0 .. cnt).rev() {
+            for (ind1, ind2) in SortIndex::new(0, n, n, cnt, i, rev) {
+                if is_less(data.key(ind2), data.key(ind1)) {
+                    data.swap_at(ind1, ind2);
+                }
+            }
+        }
+        rev = !rev;
+    }
+}
+
+/// Sorts `data` by walking the bitonic network stage by stage.
+pub fn bitonic_i_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    bitonic_i_sort_impl(&mut &mut *data, &mut T::lt);
+}
+
+/// Sorts `data` by walking the bitonic network stage by stage according to
+/// `compare`.
+pub fn bitonic_i_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    bitonic_i_sort_impl(&mut &mut *data, &mut |a: &T, b: &T| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` by walking the bitonic network stage by stage by the key
+/// extracted with `f`.
+pub fn bitonic_i_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    bitonic_i_sort_impl(&mut &mut *data, &mut |a: &T, b: &T| f(a) < f(b));
+}
+//}}}
+//{{{ Bitonic sort, iteration, parallel
+use std::sync::{Mutex, PoisonError};
+use crate::pool::{self, Barrier, Pool};
+
+// Whether the comparator between `ind` and `ind | 1 << i` at stage `cnt`
+// puts the lesser element at `ind`, as in `SortIndex`.
+fn ascending(ind: usize, cnt: u32, rev: bool) -> bool {
+    ((ind >> cnt).count_ones() & 1 == 1) == rev
+}
+
+fn out_of_order<T, F>(x: &T, y: &T, ascending: bool, is_less: &F) -> bool
+where F: Fn(&T, &T) -> bool {
+    if ascending { is_less(y, x) } else { is_less(x, y) }
+}
+
+// Runs the comparators of distance `dist` within `data`, which starts at
+// index `base` of the whole slice.
+fn exchange_within<L, F>(data: &mut L, base: usize, dist: usize, cnt: u32, rev: bool,
+                         is_less: &F)
+where L: Lanes, F: Fn(&L::Key, &L::Key) -> bool {
+    for ind in (0 .. data.len()).filter(|ind| ind & dist == 0) {
+        if ind | dist >= data.len() { break; }
+        let ascending = ascending(base + ind, cnt, rev);
+        if out_of_order(data.key(ind), data.key(ind | dist), ascending, is_less) {
+            data.swap_at(ind, ind | dist);
+        }
+    }
+}
+
+// Runs the comparators between `x` and `y`, all in the same direction.
+fn exchange_between<L, F>(x: &mut L, y: &mut L, ascending: bool, is_less: &F)
+where L: Lanes, F: Fn(&L::Key, &L::Key) -> bool {
+    for ind in 0 .. x.len().min(y.len()) {
+        if out_of_order(x.key(ind), y.key(ind), ascending, is_less) {
+            x.swap_with(y, ind);
+        }
+    }
+}
+
+// Runs the network on `2^t_depth` workers, or on the workers of `pool`, over
+// the `n` elements cut by `split` into pieces of the given length.
+//
+// There are twice as many pieces as workers. In a stage of distance below
+// the piece length, worker `j` sorts within the pieces `2j` and `2j + 1`;
+// otherwise it takes the `j`-th pair of pieces at that distance. Pieces thus
+// change hands only between stages, which a barrier separates, and each is
+// borrowed through its own uncontended mutex.
+pub(super) fn bitonic_ip_sort_impl<L, F>(n: usize, split: impl FnOnce(usize) -> Vec<L>,
+                                         t_depth: u32, pool: Option<&Pool>, is_less: &F)
+where L: Lanes + Send, F: Fn(&L::Key, &L::Key) -> bool + Sync {
+    if n <= 1 { return; }
+    let depth = {
+        let (mut depth, mut n) = (1u32, n - 1 >> 1);
+        while n != 0 { n >>= 1; depth += 1; }
+        depth
+    };
+    let t_depth = pool.map_or(t_depth, |pool| pool.threads().ilog2()).min(depth - 1);
+    let piece = 1usize << (depth - t_depth - 1);
+    #[cfg(feature = "rayon")]
+    if pool.is_none() { return bitonic_ip_sort_rayon(split(piece), piece, depth, is_less); }
+    let t_n = 1usize << t_depth;
+    let pieces = split(piece).into_iter().map(Mutex::new).collect::<Vec<_>>();
+    let lock = |p: usize| pieces.get(p)
+        .map(|m| m.lock().unwrap_or_else(PoisonError::into_inner));
+    let barrier = Barrier::new(t_n);
+    let job = |j: usize| {
+        if j >= t_n { return; }
+        let _guard = barrier.poison_on_panic();
+        let mut rev = depth & 1 == 0;
+        for cnt in 1 ..= depth {
+            for i in (0 .. cnt).rev() {
+                let dist = 1usize << i;
+                if dist < piece {
+                    for p in [2 * j, 2 * j + 1] {
+                        let Some(mut data) = lock(p) else { continue };
+                        exchange_within(&mut *data, p * piece, dist, cnt, rev, is_less);
+                    }
+                } else {
+                    let m = dist / piece;
+                    let p = (j & !(m - 1)) << 1 | j & (m - 1);
+                    if let (Some(mut x), Some(mut y)) = (lock(p), lock(p | m)) {
+                        exchange_between(&mut *x, &mut *y, ascending(p * piece, cnt, rev), is_less);
+                    }
+                }
+                if !barrier.wait() { return; }
+            }
+            rev = !rev;
+        }
+    };
+    match pool {
+        Some(pool) => pool.run(&job),
+        None => pool::run_scoped(t_n, &job),
+    }
+}
+
+// Runs the network stage by stage on the current rayon pool, handing the
+// same pieces as above out anew in every stage.
+#[cfg(feature = "rayon")]
+fn bitonic_ip_sort_rayon<L, F>(mut pieces: Vec<L>, piece: usize, depth: u32, is_less: &F)
+where L: Lanes + Send, F: Fn(&L::Key, &L::Key) -> bool + Sync {
+    use rayon::prelude::*;
+    let mut rev = depth & 1 == 0;
+    for cnt in 1 ..= depth {
+        for i in (0 .. cnt).rev() {
+            let dist = 1usize << i;
+            if dist < piece {
+                pieces.par_iter_mut().enumerate().for_each(|(p, data)| {
+                    exchange_within(data, p * piece, dist, cnt, rev, is_less);
+                });
+            } else {
+                let m = dist / piece;
+                pieces.par_chunks_mut(2 * m).enumerate().for_each(|(b, pieces)| {
+                    let ascending = ascending(b * 2 * dist, cnt, rev);
+                    let (x, y) = pieces.split_at_mut(m.min(pieces.len()));
+                    x.par_iter_mut().zip(y).for_each(|(x, y)| {
+                        exchange_between(x, y, ascending, is_less);
+                    });
+                });
+            }
+        }
+        rev = !rev;
+    }
+}
+
+/// Sorts `data` by walking the bitonic network stage by stage, splitting
+/// every stage among `2^t_depth` threads.
+///
+/// The threads are started once per call and synchronized by a barrier
+/// between stages; with the `rayon` feature, every stage is split among
+/// tasks of the rayon pool instead. Use [`bitonic_ip_sort_with`] to run on a
+/// caller-owned [`Pool`] instead. A panic of `is_less` on any thread is
+/// resumed on the calling thread once all threads are done.
+pub fn bitonic_ip_sort<T>(data: &mut [T], t_depth: u32)
+where T: PartialOrd + Send {
+    bitonic_ip_sort_impl(data.len(), |piece| data.chunks_mut(piece).collect(),
+                         t_depth, None, &T::lt);
+}
+
+/// Parallel [`bitonic_i_sort_by`] on `2^t_depth` threads.
+pub fn bitonic_ip_sort_by<T, F>(data: &mut [T], t_depth: u32, compare: F)
+where T: Send, F: Fn(&T, &T) -> Ordering + Sync {
+    bitonic_ip_sort_impl(data.len(), |piece| data.chunks_mut(piece).collect(),
+                         t_depth, None, &|a: &T, b: &T| compare(a, b) == Ordering::Less);
+}
+
+/// Parallel [`bitonic_i_sort_by_key`] on `2^t_depth` threads.
+pub fn bitonic_ip_sort_by_key<T, K, F>(data: &mut [T], t_depth: u32, f: F)
+where T: Send, K: PartialOrd, F: Fn(&T) -> K + Sync {
+    bitonic_ip_sort_impl(data.len(), |piece| data.chunks_mut(piece).collect(),
+                         t_depth, None, &|a: &T, b: &T| f(a) < f(b));
+}
+
+/// Sorts `data` like [`bitonic_ip_sort`] on the threads of `pool`, using the
+/// largest power of two of them not above [`Pool::threads`].
+pub fn bitonic_ip_sort_with<T>(data: &mut [T], pool: &Pool)
+where T: PartialOrd + Send {
+    bitonic_ip_sort_impl(data.len(), |piece| data.chunks_mut(piece).collect(),
+                         0, Some(pool), &T::lt);
+}
+
+/// [`bitonic_ip_sort_by`] on the threads of `pool`.
+pub fn bitonic_ip_sort_by_with<T, F>(data: &mut [T], pool: &Pool, compare: F)
+where T: Send, F: Fn(&T, &T) -> Ordering + Sync {
+    bitonic_ip_sort_impl(data.len(), |piece| data.chunks_mut(piece).collect(),
+                         0, Some(pool), &|a: &T, b: &T| compare(a, b) == Ordering::Less);
+}
+
+/// [`bitonic_ip_sort_by_key`] on the threads of `pool`.
+pub fn bitonic_ip_sort_by_key_with<T, K, F>(data: &mut [T], pool: &Pool, f: F)
+where T: Send, K: PartialOrd, F: Fn(&T) -> K + Sync {
+    bitonic_ip_sort_impl(data.len(), |piece| data.chunks_mut(piece).collect(),
+                         0, Some(pool), &|a: &T, b: &T| f(a) < f(b));
+}
+//}}}
+//}}}
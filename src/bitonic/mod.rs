@@ -0,0 +1,41 @@
+//! Bitonic sorting networks for slices of arbitrary length.
+//!
+//! The recursive variants split the slice at the largest power of two below
+//! its length, the iterative variants walk the same network stage by stage.
+//! The parallel variants take a thread depth `t_depth` and run on
+//! `2^t_depth` threads borrowing disjoint parts of the slice, or on rayon
+//! tasks with the `rayon` feature; the iterative one can also run on a
+//! caller-owned [`Pool`](crate::pool::Pool).
+//!
+//! The network compares and exchanges fixed positions whatever the data, so
+//! the iterative variants also sort keys together with a slice of values
+//! (`*_sort_pairs`), or return the sorting permutation of the keys
+//! (`*_argsort`). [`SortingNetwork`] holds the comparators of the network
+//! as layers, to apply them to any slice or to export them, and checks
+//! that they sort by the 0-1 principle, as [`verify_zero_one`] does for the
+//! sort functions.
+//!
+//! Next to the parallel bitonic sorts, [`sample_p_sort`] sorts in parallel
+//! by buckets: a sorted random sample of the slice picks splitters which cut
+//! the value range into buckets of about the same size. The slice is cut into
+//! `2^t_depth` chunks which are classified against the splitters in
+//! parallel, every element is scattered into its bucket, and the buckets are
+//! sorted concurrently with one of the serial sorts of the crate, see
+//! [`BucketSort`].
+mod recursion;
+mod iteration;
+mod pairs;
+mod network;
+mod sample;
+pub use recursion::{bitonic_r_sort, bitonic_r_sort_by, bitonic_r_sort_by_key};
+pub use recursion::{bitonic_rp_sort, bitonic_rp_sort_by, bitonic_rp_sort_by_key};
+pub use iteration::{bitonic_i_sort, bitonic_i_sort_by, bitonic_i_sort_by_key};
+pub use iteration::{bitonic_ip_sort, bitonic_ip_sort_by, bitonic_ip_sort_by_key};
+pub use iteration::{bitonic_ip_sort_with, bitonic_ip_sort_by_with, bitonic_ip_sort_by_key_with};
+pub use pairs::{bitonic_i_sort_pairs, bitonic_i_sort_pairs_by, bitonic_i_sort_pairs_by_key};
+pub use pairs::{bitonic_ip_sort_pairs, bitonic_ip_sort_pairs_by, bitonic_ip_sort_pairs_by_key};
+pub use pairs::{bitonic_i_argsort, bitonic_i_argsort_by, bitonic_i_argsort_by_key};
+pub use pairs::{bitonic_ip_argsort, bitonic_ip_argsort_by, bitonic_ip_argsort_by_key};
+pub use network::{SortingNetwork, verify_zero_one, verify_zero_one_random};
+pub use sample::{sample_p_sort, sample_p_sort_by, sample_p_sort_by_key, BucketSort};
+pub use sample::{sample_p_sort_with, sample_p_sort_by_with, sample_p_sort_by_key_with};
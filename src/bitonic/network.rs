@@ -0,0 +1,323 @@
+//{{{ Struct `SortingNetwork`
+use std::cmp::Ordering;
+use std::fmt::Write;
+use super::iteration::SortIndex;
+use crate::small::{SMALL_MAX, NETWORKS};
+
+/// A sorting network on `n` wires: layers of comparators `(i, j)`, each of
+/// which puts the lesser of `data[i]` and `data[j]` at `i`. The comparators
+/// of a layer touch disjoint wires, so they may run in any order or at once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortingNetwork {
+    n      : usize,
+    layers : Vec<Vec<(usize, usize)>>,
+}
+
+impl SortingNetwork {
+    /// Builds the network from its layers.
+    ///
+    /// # Panics
+    /// Panics if a comparator leaves the `n` wires or joins a wire to
+    /// itself, or if two comparators of a layer share a wire.
+    pub fn new(n: usize, layers: Vec<Vec<(usize, usize)>>) -> Self {
+        let mut used = vec![usize::MAX; n];
+        for (l, layer) in layers.iter().enumerate() {
+            for &(i, j) in layer {
+                assert!(i < n && j < n && i != j, "bad comparator ({}, {})", i, j);
+                assert!(used[i] != l && used[j] != l, "wire shared in layer {}", l);
+                (used[i], used[j]) = (l, l);
+            }
+        }
+        SortingNetwork { n, layers }
+    }
+
+    /// The network walked by [`bitonic_i_sort`](super::bitonic_i_sort) on
+    /// slices of length `n`, one layer per stage.
+    pub fn bitonic(n: usize) -> Self {
+        let mut layers = vec![];
+        if n > 1 {
+            let depth = {
+                let (mut depth, mut n) = (1u32, n - 1 >> 1);
+                while n != 0 { n >>= 1; depth += 1; }
+                depth
+            };
+            let mut rev = depth & 1 == 0;
+            for cnt in 1 ..= depth {
+                for i in (0 .. cnt).rev() {
+                    let layer = SortIndex::new(0, n, n, cnt, i, rev).collect::<Vec<_>>();
+                    if !layer.is_empty() { layers.push(layer); }
+                }
+                rev = !rev;
+            }
+        }
+        SortingNetwork { n, layers }
+    }
+
+    /// The smallest network known on `n` wires, as used by
+    /// [`sort_small`](crate::small::sort_small): optimal in size up to 10
+    /// wires, the best known beyond.
+    ///
+    /// # Panics
+    /// Panics if `n` exceeds [`SMALL_MAX`](crate::small::SMALL_MAX).
+    pub fn optimal(n: usize) -> Self {
+        assert!(n <= SMALL_MAX, "no fixed network on {} wires", n);
+        let layers = NETWORKS[n].iter().map(|layer| layer.to_vec()).collect();
+        SortingNetwork { n, layers }
+    }
+
+    /// Number of wires.
+    pub fn len(&self) -> usize { self.n }
+
+    /// Whether the network has no wires.
+    pub fn is_empty(&self) -> bool { self.n == 0 }
+
+    /// Number of comparators.
+    pub fn size(&self) -> usize { self.layers.iter().map(Vec::len).sum() }
+
+    /// Number of layers.
+    pub fn depth(&self) -> usize { self.layers.len() }
+
+    /// The layers of comparators.
+    pub fn layers(&self) -> &[Vec<(usize, usize)>] { &self.layers }
+
+    fn apply_impl<T, F>(&self, data: &mut [T], is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        assert_eq!(data.len(), self.n);
+        for &(i, j) in self.layers.iter().flatten() {
+            if is_less(&data[j], &data[i]) { data.swap(i, j); }
+        }
+    }
+
+    /// Runs every comparator of the network on `data`.
+    ///
+    /// # Panics
+    /// Panics if the length of `data` is not [`len`](Self::len).
+    pub fn apply<T>(&self, data: &mut [T])
+    where T: PartialOrd {
+        self.apply_impl(data, &mut T::lt);
+    }
+
+    /// [`apply`](Self::apply) according to `compare`.
+    pub fn apply_by<T, F>(&self, data: &mut [T], mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering {
+        self.apply_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+    }
+
+    /// [`apply`](Self::apply) by the key extracted with `f`.
+    pub fn apply_by_key<T, K, F>(&self, data: &mut [T], mut f: F)
+    where K: PartialOrd, F: FnMut(&T) -> K {
+        self.apply_impl(data, &mut |a, b| f(a) < f(b));
+    }
+}
+//}}}
+//{{{ Export
+impl SortingNetwork {
+    /// The network as a JSON object `{"n": n, "layers": [[[i, j], …], …]}`.
+    pub fn to_json(&self) -> String {
+        let layers = self.layers.iter().map(|layer| {
+            let layer = layer.iter().map(|(i, j)| format!("[{}, {}]", i, j)).collect::<Vec<_>>();
+            format!("[{}]", layer.join(", "))
+        }).collect::<Vec<_>>();
+        format!("{{\"n\": {}, \"layers\": [{}]}}", self.n, layers.join(", "))
+    }
+
+    /// The network as a Graphviz DOT digraph: every wire is a chain of points
+    /// from left to right, one per layer, and every comparator an arrow
+    /// between two points of its layer, pointing to where the greater
+    /// element goes.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n  rankdir=LR;\n  node [shape=point];\n");
+        for w in 0 .. self.n {
+            let chain = (0 ..= self.depth()).map(|l| format!("w{}_{}", w, l)).collect::<Vec<_>>();
+            writeln!(dot, "  {} [arrowhead=none];", chain.join(" -> ")).unwrap();
+        }
+        for (l, layer) in self.layers.iter().enumerate() {
+            let rank = (0 .. self.n).map(|w| format!("w{}_{};", w, l + 1)).collect::<String>();
+            writeln!(dot, "  {{ rank=same; {} }}", rank).unwrap();
+            for &(i, j) in layer {
+                writeln!(dot, "  w{}_{} -> w{}_{} [constraint=false];", i, l + 1, j, l + 1).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The network as a Knuth diagram in SVG: wires are horizontal lines
+    /// numbered from the top, and every comparator a vertical line from a dot
+    /// on the wire which gets the lesser element to an arrow on the one which
+    /// gets the greater element. Overlapping comparators of a layer are
+    /// drawn side by side.
+    pub fn to_svg(&self) -> String {
+        const STEP: usize = 20;
+        const MARGIN: usize = 20;
+        // Columns of comparators, each with the layer it belongs to.
+        let mut columns = Vec::<(usize, Vec<(usize, usize)>)>::new();
+        for (l, layer) in self.layers.iter().enumerate() {
+            let first = columns.len();
+            for &(i, j) in layer {
+                let (lo, hi) = (i.min(j), i.max(j));
+                let free = columns[first ..].iter()
+                    .position(|(_, c)| c.iter().all(|&(a, b)| b.max(a) < lo || a.min(b) > hi));
+                match free {
+                    Some(c) => columns[first + c].1.push((i, j)),
+                    None => columns.push((l, vec![(i, j)])),
+                }
+            }
+        }
+        let (mut x, mut last) = (MARGIN, None);
+        let mut body = String::new();
+        for (l, column) in &columns {
+            x += if last == Some(*l) { STEP / 2 } else { STEP };
+            last = Some(*l);
+            for &(i, j) in column {
+                let (yi, yj) = (MARGIN + i * STEP, MARGIN + j * STEP);
+                let yj_arrow = if yj > yi { yj - 4 } else { yj + 4 };
+                writeln!(body, "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" \
+                                marker-end=\"url(#arrow)\"/>", x, yi, x, yj_arrow).unwrap();
+                writeln!(body, "  <circle cx=\"{}\" cy=\"{}\" r=\"3\"/>", x, yi).unwrap();
+            }
+        }
+        let width = x + STEP + MARGIN;
+        let height = MARGIN * 2 + self.n.saturating_sub(1) * STEP;
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" \
+                               width=\"{}\" height=\"{}\">\n", width, height);
+        svg.push_str("  <defs><marker id=\"arrow\" markerWidth=\"6\" markerHeight=\"6\" \
+                      refX=\"3\" refY=\"3\" orient=\"auto\">\
+                      <path d=\"M0,0 L6,3 L0,6 z\"/></marker></defs>\n");
+        svg.push_str("  <g stroke=\"black\" fill=\"black\">\n");
+        for w in 0 .. self.n {
+            let y = MARGIN + w * STEP;
+            writeln!(svg, "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
+                     MARGIN, y, width - MARGIN, y).unwrap();
+        }
+        svg.push_str(&body);
+        svg.push_str("  </g>\n</svg>\n");
+        svg
+    }
+}
+//}}}
+//{{{ Verification
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rand::seq::SliceRandom;
+
+// Networks up to this many wires are verified on all binary inputs.
+const EXHAUSTIVE_LIMIT: usize = 20;
+// Random binary inputs tried on larger networks.
+const RANDOM_TRIALS: usize = 1 << 16;
+
+// The binary input `ind` on `n` wires: wire `w` carries bit `w` of `ind`.
+fn binary_input(n: usize, ind: u64) -> Vec<bool> {
+    (0 .. n).map(|w| ind >> w & 1 == 1).collect()
+}
+
+// A random binary input on `n` wires: a random number of ones on random
+// wires, i.e. a random permutation cut at a random threshold.
+fn random_input(n: usize, rng: &mut StdRng) -> Vec<bool> {
+    let ones = rng.gen_range(0 ..= n);
+    let mut input = (0 .. n).map(|w| w < ones).collect::<Vec<_>>();
+    input.shuffle(rng);
+    input
+}
+
+impl SortingNetwork {
+    // Runs the network on 64 binary inputs at once, bit `b` of `wires[w]`
+    // being wire `w` of input `b`, and returns the first input it leaves
+    // unsorted, if any.
+    fn check_lanes(&self, mut wires: Vec<u64>) -> Option<usize> {
+        for &(i, j) in self.layers.iter().flatten() {
+            (wires[i], wires[j]) = (wires[i] & wires[j], wires[i] | wires[j]);
+        }
+        let bad = wires.windows(2).fold(0, |bad, w| bad | w[0] & !w[1]);
+        (bad != 0).then(|| bad.trailing_zeros() as usize)
+    }
+
+    /// Checks the network on all `2^n` binary inputs, which by the 0-1
+    /// principle proves that it sorts any input. Returns a binary input it
+    /// leaves unsorted otherwise. 64 inputs run at once as the bits of a
+    /// word, which makes `n` up to about 30 feasible.
+    ///
+    /// # Panics
+    /// Panics if `n` is 64 or more.
+    pub fn verify_exhaustive(&self) -> Result<(), Vec<bool>> {
+        let n = self.n;
+        assert!(n < 64);
+        const LOW: [u64; 6] = [0xaaaaaaaaaaaaaaaa, 0xcccccccccccccccc, 0xf0f0f0f0f0f0f0f0,
+                               0xff00ff00ff00ff00, 0xffff0000ffff0000, 0xffffffff00000000];
+        for block in 0 .. 1u64 << n.saturating_sub(6) {
+            let wires = (0 .. n).map(|w| if w < 6 {
+                LOW[w]
+            } else {
+                0u64.wrapping_sub(block >> (w - 6) & 1)
+            }).collect();
+            if let Some(b) = self.check_lanes(wires) {
+                return Err(binary_input(n, block << 6 | b as u64));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the network on `trials` random binary inputs drawn from a
+    /// generator seeded with `seed`, each a random number of ones on random
+    /// wires. Returns a binary input it leaves unsorted, if found.
+    pub fn verify_random(&self, trials: usize, seed: u64) -> Result<(), Vec<bool>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0 .. trials.div_ceil(64) {
+            let inputs = (0 .. 64).map(|_| random_input(self.n, &mut rng)).collect::<Vec<_>>();
+            let wires = (0 .. self.n).map(|w| {
+                inputs.iter().rev().fold(0, |word, input| word << 1 | input[w] as u64)
+            }).collect();
+            if let Some(b) = self.check_lanes(wires) {
+                return Err(inputs[b].clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the network on all binary inputs up to 20 wires, or on 65536
+    /// random ones beyond, see [`verify_exhaustive`](Self::verify_exhaustive)
+    /// and [`verify_random`](Self::verify_random).
+    pub fn verify(&self) -> Result<(), Vec<bool>> {
+        if self.n <= EXHAUSTIVE_LIMIT {
+            self.verify_exhaustive()
+        } else {
+            self.verify_random(RANDOM_TRIALS, self.n as u64)
+        }
+    }
+}
+
+/// Checks that `sort` sorts all `2^n` binary slices of length `n`, and
+/// returns an input it leaves unsorted otherwise.
+///
+/// If `sort` runs a sorting network, i.e. compares and exchanges the same
+/// positions whatever the data, the 0-1 principle makes this a proof that it
+/// sorts any slice of length `n`, as for the bitonic sorts of this module.
+///
+/// # Panics
+/// Panics if `n` is 64 or more.
+pub fn verify_zero_one<F>(n: usize, mut sort: F) -> Result<(), Vec<bool>>
+where F: FnMut(&mut [bool]) {
+    assert!(n < 64);
+    for ind in 0 .. 1u64 << n {
+        let input = binary_input(n, ind);
+        let mut data = input.clone();
+        sort(&mut data);
+        if data.windows(2).any(|w| w[0] && !w[1]) { return Err(input); }
+    }
+    Ok(())
+}
+
+/// Checks that `sort` sorts `trials` random binary slices of length `n`,
+/// drawn like in [`SortingNetwork::verify_random`].
+pub fn verify_zero_one_random<F>(n: usize, trials: usize, seed: u64, mut sort: F)
+    -> Result<(), Vec<bool>>
+where F: FnMut(&mut [bool]) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0 .. trials {
+        let input = random_input(n, &mut rng);
+        let mut data = input.clone();
+        sort(&mut data);
+        if data.windows(2).any(|w| w[0] && !w[1]) { return Err(input); }
+    }
+    Ok(())
+}
+//}}}
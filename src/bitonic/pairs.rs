@@ -0,0 +1,125 @@
+//{{{ Bitonic sort, key-value pairs
+use std::cmp::Ordering;
+use super::iteration::{bitonic_i_sort_impl, bitonic_ip_sort_impl};
+
+//{{{ Pairs, serial
+/// Sorts `keys` like [`bitonic_i_sort`](super::bitonic_i_sort), applying
+/// every exchange of two keys to the same positions of `values`.
+///
+/// # Panics
+/// Panics if `keys` and `values` differ in length.
+pub fn bitonic_i_sort_pairs<K, V>(keys: &mut [K], values: &mut [V])
+where K: PartialOrd {
+    assert_eq!(keys.len(), values.len());
+    bitonic_i_sort_impl(&mut (keys, values), &mut K::lt);
+}
+
+/// [`bitonic_i_sort_pairs`] according to `compare`.
+pub fn bitonic_i_sort_pairs_by<K, V, F>(keys: &mut [K], values: &mut [V], mut compare: F)
+where F: FnMut(&K, &K) -> Ordering {
+    assert_eq!(keys.len(), values.len());
+    bitonic_i_sort_impl(&mut (keys, values),
+                        &mut |a: &K, b: &K| compare(a, b) == Ordering::Less);
+}
+
+/// [`bitonic_i_sort_pairs`] by the key extracted from `keys` with `f`.
+pub fn bitonic_i_sort_pairs_by_key<K, V, X, F>(keys: &mut [K], values: &mut [V], mut f: F)
+where X: PartialOrd, F: FnMut(&K) -> X {
+    assert_eq!(keys.len(), values.len());
+    bitonic_i_sort_impl(&mut (keys, values), &mut |a: &K, b: &K| f(a) < f(b));
+}
+//}}}
+//{{{ Pairs, parallel
+/// Sorts `keys` like [`bitonic_ip_sort`](super::bitonic_ip_sort) on
+/// `2^t_depth` threads, applying every exchange of two keys to the same
+/// positions of `values`.
+///
+/// # Panics
+/// Panics if `keys` and `values` differ in length.
+pub fn bitonic_ip_sort_pairs<K, V>(keys: &mut [K], values: &mut [V], t_depth: u32)
+where K: PartialOrd + Send, V: Send {
+    assert_eq!(keys.len(), values.len());
+    bitonic_ip_sort_impl(keys.len(),
+                         |piece| keys.chunks_mut(piece).zip(values.chunks_mut(piece)).collect(),
+                         t_depth, None, &K::lt);
+}
+
+/// [`bitonic_ip_sort_pairs`] according to `compare`.
+pub fn bitonic_ip_sort_pairs_by<K, V, F>(keys: &mut [K], values: &mut [V], t_depth: u32,
+                                         compare: F)
+where K: Send, V: Send, F: Fn(&K, &K) -> Ordering + Sync {
+    assert_eq!(keys.len(), values.len());
+    bitonic_ip_sort_impl(keys.len(),
+                         |piece| keys.chunks_mut(piece).zip(values.chunks_mut(piece)).collect(),
+                         t_depth, None, &|a: &K, b: &K| compare(a, b) == Ordering::Less);
+}
+
+/// [`bitonic_ip_sort_pairs`] by the key extracted from `keys` with `f`.
+pub fn bitonic_ip_sort_pairs_by_key<K, V, X, F>(keys: &mut [K], values: &mut [V], t_depth: u32,
+                                                f: F)
+where K: Send, V: Send, X: PartialOrd, F: Fn(&K) -> X + Sync {
+    assert_eq!(keys.len(), values.len());
+    bitonic_ip_sort_impl(keys.len(),
+                         |piece| keys.chunks_mut(piece).zip(values.chunks_mut(piece)).collect(),
+                         t_depth, None, &|a: &K, b: &K| f(a) < f(b));
+}
+//}}}
+//{{{ Argsort
+/// Returns the permutation `perm` which sorts `keys`, i.e. `keys[perm[0]]`,
+/// `keys[perm[1]]`, … ascend, leaving `keys` as is.
+///
+/// The indices go through the same exchanges as the values of
+/// [`bitonic_i_sort_pairs`] starting from `0 .. n`.
+pub fn bitonic_i_argsort<K>(keys: &[K]) -> Vec<usize>
+where K: PartialOrd {
+    let mut perm = (0 .. keys.len()).collect::<Vec<_>>();
+    bitonic_i_sort_impl(&mut perm.as_mut_slice(), &mut |&a: &usize, &b: &usize| keys[a] < keys[b]);
+    perm
+}
+
+/// [`bitonic_i_argsort`] according to `compare`.
+pub fn bitonic_i_argsort_by<K, F>(keys: &[K], mut compare: F) -> Vec<usize>
+where F: FnMut(&K, &K) -> Ordering {
+    let mut perm = (0 .. keys.len()).collect::<Vec<_>>();
+    bitonic_i_sort_impl(&mut perm.as_mut_slice(),
+                        &mut |&a: &usize, &b: &usize| compare(&keys[a], &keys[b]) == Ordering::Less);
+    perm
+}
+
+/// [`bitonic_i_argsort`] by the key extracted from `keys` with `f`.
+pub fn bitonic_i_argsort_by_key<K, X, F>(keys: &[K], mut f: F) -> Vec<usize>
+where X: PartialOrd, F: FnMut(&K) -> X {
+    let mut perm = (0 .. keys.len()).collect::<Vec<_>>();
+    bitonic_i_sort_impl(&mut perm.as_mut_slice(),
+                        &mut |&a: &usize, &b: &usize| f(&keys[a]) < f(&keys[b]));
+    perm
+}
+
+/// Parallel [`bitonic_i_argsort`] on `2^t_depth` threads.
+pub fn bitonic_ip_argsort<K>(keys: &[K], t_depth: u32) -> Vec<usize>
+where K: PartialOrd + Sync {
+    let mut perm = (0 .. keys.len()).collect::<Vec<_>>();
+    bitonic_ip_sort_impl(perm.len(), |piece| perm.chunks_mut(piece).collect(),
+                         t_depth, None, &|&a: &usize, &b: &usize| keys[a] < keys[b]);
+    perm
+}
+
+/// Parallel [`bitonic_i_argsort_by`] on `2^t_depth` threads.
+pub fn bitonic_ip_argsort_by<K, F>(keys: &[K], t_depth: u32, compare: F) -> Vec<usize>
+where K: Sync, F: Fn(&K, &K) -> Ordering + Sync {
+    let mut perm = (0 .. keys.len()).collect::<Vec<_>>();
+    bitonic_ip_sort_impl(perm.len(), |piece| perm.chunks_mut(piece).collect(), t_depth, None,
+                         &|&a: &usize, &b: &usize| compare(&keys[a], &keys[b]) == Ordering::Less);
+    perm
+}
+
+/// Parallel [`bitonic_i_argsort_by_key`] on `2^t_depth` threads.
+pub fn bitonic_ip_argsort_by_key<K, X, F>(keys: &[K], t_depth: u32, f: F) -> Vec<usize>
+where K: Sync, X: PartialOrd, F: Fn(&K) -> X + Sync {
+    let mut perm = (0 .. keys.len()).collect::<Vec<_>>();
+    bitonic_ip_sort_impl(perm.len(), |piece| perm.chunks_mut(piece).collect(), t_depth, None,
+                         &|&a: &usize, &b: &usize| f(&keys[a]) < f(&keys[b]));
+    perm
+}
+//}}}
+//}}}
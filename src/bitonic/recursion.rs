@@ -0,0 +1,124 @@
+//{{{ Bitonic sort, recursion
+use std::cmp::Ordering;
+use crate::small::{SMALL_MAX, sort_small_impl};
+fn bitonic_divide(n: usize) -> usize {
+    let (mut ind, mut n) = (1usize, n - 1 >> 1);
+    while n != 0 { n >>= 1; ind <<= 1; }
+    ind
+}
+fn bitonic_merge<T, F>(data: &mut [T], rev: bool, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    if data.len() <= 1 { return; }
+    let n = data.len();
+    let ind = bitonic_divide(n);
+    {
+        let (data, data2) = data.split_at_mut(ind);
+        let data1 = &mut data[.. n - ind];
+        let (data1, data2) = if rev { (data2, data1) } else { (data1, data2) };
+        for (x, y) in data1.iter_mut().zip(data2) {
+            if is_less(y, x) {
+                std::mem::swap(x, y);
+            }
+        }
+    }
+    bitonic_merge(&mut data[.. ind], rev, is_less);
+    bitonic_merge(&mut data[ind ..], rev, is_less);
+}
+// Sorts tiny `data` with a fixed network, descending if `rev`, in place of
+// the bitonic network of its length.
+fn bitonic_sort_small<T, F>(data: &mut [T], rev: bool, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    if rev {
+        sort_small_impl(data, &mut |a, b| is_less(b, a));
+    } else {
+        sort_small_impl(data, is_less);
+    }
+}
+//{{{ Bitonic sort, recursion, serial
+fn bitonic_r_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    fn bitonic_sort<T, F>(data: &mut [T], rev: bool, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        if data.len() <= SMALL_MAX {
+            bitonic_sort_small(data, rev, is_less);
+            return;
+        }
+        let ind = bitonic_divide(data.len());
+        let (data1, data2) = data.split_at_mut(ind);
+        bitonic_sort(data1, !rev, is_less);
+        bitonic_sort(data2, rev, is_less);
+        bitonic_merge(data, rev, is_less);
+    }
+    bitonic_sort(data, false, is_less);
+}
+
+/// Sorts `data` with the recursive bitonic network on the current thread.
+///
+/// Subsequences up to [`SMALL_MAX`](crate::small::SMALL_MAX) elements are
+/// sorted by the smaller fixed networks of [`small`](crate::small), so the
+/// whole is still a sorting network.
+pub fn bitonic_r_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    bitonic_r_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with the recursive bitonic network according to `compare`.
+pub fn bitonic_r_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    bitonic_r_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with the recursive bitonic network by the key extracted
+/// with `f`.
+pub fn bitonic_r_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    bitonic_r_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
+//{{{ Bitonic sort, recursion, parallel
+use crate::pool;
+fn bitonic_rp_sort_impl<T, F>(data: &mut [T], t_depth: u32, is_less: &F)
+where T: Send, F: Fn(&T, &T) -> bool + Sync {
+    fn bitonic_sort<T, F>(count: u32, data: &mut [T], rev: bool, is_less: &F)
+    where T: Send, F: Fn(&T, &T) -> bool + Sync {
+        if data.len() <= SMALL_MAX {
+            bitonic_sort_small(data, rev, &mut &*is_less);
+            return;
+        }
+        let ind = bitonic_divide(data.len());
+        let (data1, data2) = data.split_at_mut(ind);
+        if count == 0 {
+            bitonic_sort(0, data1, !rev, is_less);
+            bitonic_sort(0, data2, rev, is_less);
+        } else {
+            pool::join(|| bitonic_sort(count - 1, data2, rev, is_less),
+                       || bitonic_sort(count - 1, data1, !rev, is_less));
+        }
+        bitonic_merge(data, rev, &mut &*is_less);
+    }
+    bitonic_sort(t_depth, data, false, is_less);
+}
+
+/// Sorts `data` with the recursive bitonic network, forking the first
+/// `t_depth` levels of recursion onto separate threads.
+///
+/// A panic of `is_less` on any thread is resumed on the calling thread once
+/// all threads are done.
+pub fn bitonic_rp_sort<T>(data: &mut [T], t_depth: u32)
+where T: PartialOrd + Send {
+    bitonic_rp_sort_impl(data, t_depth, &T::lt);
+}
+
+/// Parallel [`bitonic_r_sort_by`] on `2^t_depth` threads.
+pub fn bitonic_rp_sort_by<T, F>(data: &mut [T], t_depth: u32, compare: F)
+where T: Send, F: Fn(&T, &T) -> Ordering + Sync {
+    bitonic_rp_sort_impl(data, t_depth, &|a: &T, b: &T| compare(a, b) == Ordering::Less);
+}
+
+/// Parallel [`bitonic_r_sort_by_key`] on `2^t_depth` threads.
+pub fn bitonic_rp_sort_by_key<T, K, F>(data: &mut [T], t_depth: u32, f: F)
+where T: Send, K: PartialOrd, F: Fn(&T) -> K + Sync {
+    bitonic_rp_sort_impl(data, t_depth, &|a: &T, b: &T| f(a) < f(b));
+}
+//}}}
+//}}}
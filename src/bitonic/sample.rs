@@ -0,0 +1,210 @@
+//{{{ Sample sort
+use std::cmp::Ordering;
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+use crate::bisection::{quick_sort_impl, intro_sort_impl, pdq_sort_impl};
+use crate::bisection::{merge_sort_impl, tim_sort_impl};
+use crate::heap::heap_sort_impl;
+use crate::pool;
+
+// Slices up to this length are sorted on a single thread.
+const SEQUENTIAL_THRESHOLD: usize = 1 << 14;
+// Buckets per thread, so that a few large buckets do not keep one thread
+// busy while the others are idle.
+const BUCKETS_PER_THREAD: usize = 4;
+// Sampled elements per bucket.
+const OVERSAMPLE: usize = 32;
+
+// The part of the scratch buffer one chunk scatters into for one bucket.
+type Segment<'a, T> = std::slice::IterMut<'a, MaybeUninit<T>>;
+
+//{{{ Enum `BucketSort`
+/// The serial sort used on each bucket of a sample sort.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BucketSort {
+    /// [`quick_sort`](crate::bisection::quick_sort)
+    Quick,
+    /// [`intro_sort`](crate::bisection::intro_sort)
+    Intro,
+    /// [`pdq_sort`](crate::bisection::pdq_sort)
+    #[default]
+    Pdq,
+    /// [`merge_sort`](crate::bisection::merge_sort)
+    Merge,
+    /// [`tim_sort`](crate::bisection::tim_sort)
+    Tim,
+    /// [`heap_sort`](crate::heap::heap_sort)
+    Heap,
+}
+
+impl BucketSort {
+    fn sort<T, F>(self, data: &mut [T], is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        match self {
+            BucketSort::Quick => quick_sort_impl(data, is_less),
+            BucketSort::Intro => intro_sort_impl(data, is_less),
+            BucketSort::Pdq   => pdq_sort_impl(data, is_less),
+            BucketSort::Merge => merge_sort_impl(data, is_less),
+            BucketSort::Tim   => tim_sort_impl(data, is_less),
+            BucketSort::Heap  => heap_sort_impl(data, is_less),
+        }
+    }
+}
+//}}}
+//{{{ Sampling
+// Moves a sample of `count` elements drawn without replacement to the front
+// of `data` by a partial Fisher–Yates shuffle. The generator is seeded with
+// the length, so the sort is deterministic.
+fn draw_sample<T>(data: &mut [T], count: usize) {
+    let n = data.len();
+    let mut random = n as u64 | 1;
+    for i in 0 .. count {
+        random ^= random << 13;
+        random ^= random >> 7;
+        random ^= random << 17;
+        data.swap(i, i + (random % (n - i) as u64) as usize);
+    }
+}
+//}}}
+
+fn sample_p_sort_impl<T, F>(data: &mut [T], t_depth: u32, bucket_sort: BucketSort, is_less: &F)
+where T: Send + Sync, F: Fn(&T, &T) -> bool + Sync {
+    let n = data.len();
+    // Fewer threads for short slices, so that every bucket gets a fair share
+    // of the sample and the sample stays a small part of the slice.
+    let mut t_depth = t_depth.min(16);
+    while t_depth > 0 && n < (BUCKETS_PER_THREAD * OVERSAMPLE << t_depth) * 16 {
+        t_depth -= 1;
+    }
+    if t_depth == 0 || n <= SEQUENTIAL_THRESHOLD {
+        bucket_sort.sort(data, &mut &*is_less);
+        return;
+    }
+    let threads = 1 << t_depth;
+    let buckets = BUCKETS_PER_THREAD * threads;
+    draw_sample(data, buckets * OVERSAMPLE);
+    bucket_sort.sort(&mut data[.. buckets * OVERSAMPLE], &mut &*is_less);
+
+    let size = n.div_ceil(threads);
+    let mut ids = vec![0u32; n];
+    let mut buf = Vec::with_capacity(n);
+    let (counts, buckets, equal) = {
+        // Every element is read by bitwise copy into `buf`, which is copied
+        // back over `data` at once. `is_less` is not called in between, so a
+        // panic of it leaves `data` untouched.
+        let data: &[T] = data;
+        let mut splitters = Vec::<&T>::with_capacity(buckets - 1);
+        for x in (1 .. buckets).map(|b| &data[b * OVERSAMPLE]) {
+            match splitters.last() {
+                Some(&sp) if !is_less(sp, x) => {},
+                _ => splitters.push(x),
+            }
+        }
+        // With duplicate splitters, the elements equal to a splitter get a
+        // bucket of their own, which needs no sorting: bucket `2b` holds the
+        // elements between splitters `b - 1` and `b`, and bucket `2b + 1` the
+        // ones equal to splitter `b`.
+        let equal = splitters.len() < buckets - 1;
+        let buckets = if equal { splitters.len() * 2 + 1 } else { buckets };
+        let classify = |(chunk, ids, counts): (&[T], &mut [u32], &mut [usize])| {
+            for (x, id) in chunk.iter().zip(ids.iter_mut()) {
+                let mut b = splitters.partition_point(|sp| !is_less(x, sp));
+                if equal {
+                    b = if b > 0 && !is_less(splitters[b - 1], x) { b * 2 - 1 } else { b * 2 };
+                }
+                *id = b as u32;
+                counts[b] += 1;
+            }
+        };
+        let mut counts = vec![0usize; threads * buckets];
+        pool::for_each(data.chunks(size).zip(ids.chunks_mut(size))
+                       .zip(counts.chunks_mut(buckets))
+                       .map(|((chunk, ids), counts)| (chunk, ids, counts)).collect(),
+                       &classify);
+
+        // Bucket-major, chunk-minor: the elements of a bucket keep the order
+        // of their chunks.
+        let mut rest = &mut buf.spare_capacity_mut()[.. n];
+        let mut segments = (0 .. threads).map(|_| Vec::with_capacity(buckets)).collect::<Vec<_>>();
+        for b in 0 .. buckets {
+            for (c, segment) in segments.iter_mut().enumerate() {
+                let (head, tail) = mem::take(&mut rest).split_at_mut(counts[c * buckets + b]);
+                segment.push(head.iter_mut());
+                rest = tail;
+            }
+        }
+        let scatter = |(chunk, ids, mut segment): (&[T], &[u32], Vec<Segment<T>>)| {
+            for (x, &id) in chunk.iter().zip(ids) {
+                segment[id as usize].next().unwrap().write(unsafe { ptr::read(x) });
+            }
+        };
+        pool::for_each(data.chunks(size).zip(ids.chunks(size)).zip(segments)
+                       .map(|((chunk, ids), segment)| (chunk, ids, segment)).collect(),
+                       &scatter);
+        (counts, buckets, equal)
+    };
+    unsafe { ptr::copy_nonoverlapping(buf.as_ptr(), data.as_mut_ptr(), n); }
+
+    let mut rest = data;
+    let mut parts = Vec::with_capacity(buckets);
+    for b in 0 .. buckets {
+        let len = (0 .. threads).map(|c| counts[c * buckets + b]).sum();
+        let (head, tail) = mem::take(&mut rest).split_at_mut(len);
+        if !(equal && b % 2 == 1) { parts.push(head); }
+        rest = tail;
+    }
+    pool::for_each(parts, &|part: &mut [T]| bucket_sort.sort(part, &mut &*is_less));
+}
+
+/// Sorts `data` with sample sort on `2^t_depth` threads, sorting the buckets
+/// with [`pdq_sort`](crate::bisection::pdq_sort).
+///
+/// Splitters are taken from a sorted sample of 32 elements per bucket, with
+/// 4 buckets per thread; the elements equal to a repeated splitter get a
+/// bucket of their own, which is not sorted. Fewer threads are used for
+/// short slices, and slices below a few ten thousand elements are sorted on
+/// one thread. Uses a scratch buffer of `n` elements and `n` bucket indices.
+///
+/// Not stable, even with a stable bucket sort, since drawing the sample
+/// reorders the slice.
+pub fn sample_p_sort<T>(data: &mut [T], t_depth: u32)
+where T: PartialOrd + Send + Sync {
+    sample_p_sort_impl(data, t_depth, BucketSort::Pdq, &T::lt);
+}
+
+/// Sorts `data` with sample sort on `2^t_depth` threads according to
+/// `compare`.
+pub fn sample_p_sort_by<T, F>(data: &mut [T], t_depth: u32, compare: F)
+where T: Send + Sync, F: Fn(&T, &T) -> Ordering + Sync {
+    sample_p_sort_impl(data, t_depth, BucketSort::Pdq,
+                       &|a: &T, b: &T| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with sample sort on `2^t_depth` threads by the key extracted
+/// with `f`.
+pub fn sample_p_sort_by_key<T, K, F>(data: &mut [T], t_depth: u32, f: F)
+where T: Send + Sync, K: PartialOrd, F: Fn(&T) -> K + Sync {
+    sample_p_sort_impl(data, t_depth, BucketSort::Pdq, &|a: &T, b: &T| f(a) < f(b));
+}
+
+/// [`sample_p_sort`] sorting the buckets with `bucket_sort`.
+pub fn sample_p_sort_with<T>(data: &mut [T], t_depth: u32, bucket_sort: BucketSort)
+where T: PartialOrd + Send + Sync {
+    sample_p_sort_impl(data, t_depth, bucket_sort, &T::lt);
+}
+
+/// [`sample_p_sort_by`] sorting the buckets with `bucket_sort`.
+pub fn sample_p_sort_by_with<T, F>(data: &mut [T], t_depth: u32, bucket_sort: BucketSort,
+                                   compare: F)
+where T: Send + Sync, F: Fn(&T, &T) -> Ordering + Sync {
+    sample_p_sort_impl(data, t_depth, bucket_sort,
+                       &|a: &T, b: &T| compare(a, b) == Ordering::Less);
+}
+
+/// [`sample_p_sort_by_key`] sorting the buckets with `bucket_sort`.
+pub fn sample_p_sort_by_key_with<T, K, F>(data: &mut [T], t_depth: u32, bucket_sort: BucketSort,
+                                          f: F)
+where T: Send + Sync, K: PartialOrd, F: Fn(&T) -> K + Sync {
+    sample_p_sort_impl(data, t_depth, bucket_sort, &|a: &T, b: &T| f(a) < f(b));
+}
+//}}}
@@ -0,0 +1,169 @@
+//{{{ d-ary heap
+use std::cmp::Ordering;
+use crate::hole::Hole;
+
+// Moves `data[ind]` up the `D`-ary max heap `data[..= ind]` to its place.
+fn sift_up<const D: usize, T, F>(data: &mut [T], ind: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let mut hole = Hole::new(data, ind);
+    while hole.pos() != 0 {
+        let parent = (hole.pos() - 1) / D;
+        if !is_less(hole.get(parent), hole.element()) { break; }
+        hole.move_to(parent);
+    }
+}
+
+// Moves `data[pos]` down the `D`-ary max heap `data` to its place, bottom
+// up: the hole first goes down the path of greatest children to a leaf,
+// which costs `D - 1` comparisons per level, then the element climbs back
+// from there, usually a short way since it came from the bottom.
+fn sift_down<const D: usize, T, F>(data: &mut [T], pos: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    let mut hole = Hole::new(data, pos);
+    loop {
+        let first = hole.pos() * D + 1;
+        if first >= n { break; }
+        let mut ind_s = first;
+        for ind in first + 1 .. (first + D).min(n) {
+            if is_less(hole.get(ind_s), hole.get(ind)) { ind_s = ind; }
+        }
+        hole.move_to(ind_s);
+    }
+    while hole.pos() > pos {
+        let parent = (hole.pos() - 1) / D;
+        if !is_less(hole.get(parent), hole.element()) { break; }
+        hole.move_to(parent);
+    }
+}
+
+// Builds a `D`-ary max heap on `data` bottom up.
+fn heapify<const D: usize, T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    for ind in (0 .. data.len().saturating_sub(1).div_ceil(D)).rev() {
+        sift_down::<D, _, _>(data, ind, is_less);
+    }
+}
+
+// Sorts the `D`-ary max heap `data` by moving its maximum to the back
+// repeatedly.
+fn sort_heap<const D: usize, T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    for ind in (1 .. data.len()).rev() {
+        data.swap(0, ind);
+        sift_down::<D, _, _>(&mut data[.. ind], 0, is_less);
+    }
+}
+
+/// A max priority queue on a `D`-ary heap: node `i` has the children
+/// `D i + 1 ..= D i + D`.
+///
+/// A wider heap is shallower, so pushing costs fewer comparisons and the
+/// children of a node of small elements share a cache line, while popping
+/// compares more children per level. Popping sifts bottom up, as
+/// [`dary_heap_sort`] does.
+#[derive(Clone, Debug)]
+pub struct DaryHeap<T, const D: usize> {
+    data : Vec<T>,
+}
+
+impl<T, const D: usize> DaryHeap<T, D> {
+    // Rejects `D < 2` at compile time, wherever the heap is built or used.
+    const CHECK: () = assert!(D >= 2, "a d-ary heap needs D >= 2");
+}
+
+impl<T, const D: usize> Default for DaryHeap<T, D> {
+    fn default() -> Self {
+        let () = Self::CHECK;
+        DaryHeap { data: vec![] }
+    }
+}
+
+impl<T, const D: usize> DaryHeap<T, D>
+where T: PartialOrd {
+    /// Creates an empty heap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a heap from the elements of `data` in `O(n)`.
+    pub fn from_vec(mut data: Vec<T>) -> Self {
+        let () = Self::CHECK;
+        heapify::<D, _, _>(&mut data, &mut T::lt);
+        DaryHeap { data }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize { self.data.len() }
+
+    /// Whether the heap is empty.
+    pub fn is_empty(&self) -> bool { self.data.is_empty() }
+
+    /// The greatest element, if any.
+    pub fn peek(&self) -> Option<&T> { self.data.first() }
+
+    /// Adds `x` to the heap.
+    pub fn push(&mut self, x: T) {
+        let () = Self::CHECK;
+        self.data.push(x);
+        let ind = self.data.len() - 1;
+        sift_up::<D, _, _>(&mut self.data, ind, &mut T::lt);
+    }
+
+    /// Removes the greatest element and returns it, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        let () = Self::CHECK;
+        let last = self.data.len().checked_sub(1)?;
+        self.data.swap(0, last);
+        let x = self.data.pop();
+        if !self.data.is_empty() {
+            sift_down::<D, _, _>(&mut self.data, 0, &mut T::lt);
+        }
+        x
+    }
+
+    /// The elements in ascending order, popped in place.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let () = Self::CHECK;
+        sort_heap::<D, _, _>(&mut self.data, &mut T::lt);
+        self.data
+    }
+}
+
+impl<T, const D: usize> From<Vec<T>> for DaryHeap<T, D>
+where T: PartialOrd {
+    fn from(data: Vec<T>) -> Self { Self::from_vec(data) }
+}
+//}}}
+//{{{ d-ary heap sort
+fn dary_heap_sort_impl<const D: usize, T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let () = DaryHeap::<T, D>::CHECK;
+    heapify::<D, _, _>(data, is_less);
+    sort_heap::<D, _, _>(data, is_less);
+}
+
+/// Sorts `data` with heap sort on a `D`-ary heap, `D >= 2`, sifting down
+/// bottom up (Floyd, Wegener).
+///
+/// Bottom-up sifting spends `D - 1` comparisons per level on the way down
+/// and few on the way up, where a top-down sift as in
+/// [`heap_sort`](super::heap_sort) spends `D` per level. A 4-ary or 8-ary
+/// heap keeps the children of a node of small elements in one cache line.
+pub fn dary_heap_sort<const D: usize, T>(data: &mut [T])
+where T: PartialOrd {
+    dary_heap_sort_impl::<D, _, _>(data, &mut T::lt);
+}
+
+/// Sorts `data` with `D`-ary heap sort according to `compare`.
+pub fn dary_heap_sort_by<const D: usize, T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    dary_heap_sort_impl::<D, _, _>(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with `D`-ary heap sort by the key extracted with `f`.
+pub fn dary_heap_sort_by_key<const D: usize, T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    dary_heap_sort_impl::<D, _, _>(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
@@ -0,0 +1,69 @@
+//{{{ Heap sort
+use std::cmp::Ordering;
+use crate::hole::Hole;
+/// Moves `data[ind]` up the max heap `data[..= ind]` to its place.
+pub(crate) fn sift_up<T, F>(data: &mut [T], mut ind: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    while ind != 0 {
+        let parent = ind - 1 >> 1;
+        if is_less(&data[parent], &data[ind]) {
+            data.swap(ind, parent);
+        }
+        ind = parent;
+    }
+}
+
+/// Moves `data[pos]` down the max heap `data` to its place.
+pub(crate) fn sift_down<T, F>(data: &mut [T], pos: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    let mut hole = Hole::new(data, pos);
+    loop {
+        let mut index_s = hole.pos().wrapping_shl(1) + 1;
+        let index_r = index_s + 1;
+        if index_s >= n { break; }
+        if index_r < n && is_less(hole.get(index_s), hole.get(index_r)) {
+            index_s = index_r;
+        }
+        if is_less(hole.element(), hole.get(index_s)) {
+            hole.move_to(index_s);
+        } else { break; }
+    }
+}
+
+/// Sorts the max heap `data` by moving its maximum to the back repeatedly.
+pub(crate) fn sort_heap<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    for ind in (1 .. data.len()).rev() {
+        data.swap(0, ind);
+        sift_down(&mut data[.. ind], 0, is_less);
+    }
+}
+
+pub(crate) fn heap_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    if data.len() <= 1 { return; }
+    for ind in 1 .. data.len() {
+        sift_up(data, ind, is_less);
+    }
+    sort_heap(data, is_less);
+}
+
+/// Sorts `data` with binary heap sort.
+pub fn heap_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    heap_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with binary heap sort according to `compare`.
+pub fn heap_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    heap_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with binary heap sort by the key extracted with `f`.
+pub fn heap_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    heap_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
@@ -0,0 +1,105 @@
+//{{{ Struct `IncrementalSorter`
+use super::LeonardoHeap;
+
+/// Sorts a stream of elements of unknown length as they come, with the
+/// heap building phase of [`smooth_l_sort`](super::smooth_l_sort).
+///
+/// Every element pushed joins a [`LeonardoHeap`], in `O(log n)`, and `O(1)`
+/// while the stream comes in nearly ascending order; the sorted elements
+/// can be drained at any moment, in `O(n log n)` or `O(n)` for a nearly
+/// sorted stream.
+#[derive(Clone, Debug)]
+pub struct IncrementalSorter<T> {
+    heap : LeonardoHeap<T>,
+}
+
+impl<T> Default for IncrementalSorter<T> {
+    fn default() -> Self {
+        IncrementalSorter { heap: LeonardoHeap::default() }
+    }
+}
+
+impl<T> IncrementalSorter<T>
+where T: PartialOrd {
+    /// Creates a sorter with no elements.
+    pub fn new() -> Self { Self::default() }
+
+    /// Number of elements pushed since the last drain.
+    pub fn len(&self) -> usize { self.heap.len() }
+
+    /// Whether no element was pushed since the last drain.
+    pub fn is_empty(&self) -> bool { self.heap.is_empty() }
+
+    /// The greatest element pushed so far, if any.
+    pub fn peek_max(&self) -> Option<&T> { self.heap.peek() }
+
+    /// Adds `x` to the elements to sort.
+    pub fn push(&mut self, x: T) { self.heap.push(x); }
+
+    /// Removes all elements and returns them in ascending order.
+    pub fn drain_sorted(&mut self) -> std::vec::IntoIter<T> {
+        std::mem::take(&mut self.heap).into_sorted_vec().into_iter()
+    }
+
+    /// Removes all elements and yields them lazily in descending order,
+    /// each in `O(log n)`.
+    pub fn drain_lazy(&mut self) -> SortedLazy<T> {
+        SortedLazy { heap: std::mem::take(&mut self.heap) }
+    }
+
+    /// The elements in ascending order.
+    pub fn into_sorted_vec(self) -> Vec<T> { self.heap.into_sorted_vec() }
+}
+
+impl<T> Extend<T> for IncrementalSorter<T>
+where T: PartialOrd {
+    fn extend<I>(&mut self, iter: I)
+    where I: IntoIterator<Item = T> {
+        for x in iter { self.push(x); }
+    }
+}
+
+impl<T> FromIterator<T> for IncrementalSorter<T>
+where T: PartialOrd {
+    fn from_iter<I>(iter: I) -> Self
+    where I: IntoIterator<Item = T> {
+        IncrementalSorter { heap: LeonardoHeap::from_vec(iter.into_iter().collect()) }
+    }
+}
+//}}}
+//{{{ Iterator adapter `sorted_lazy`
+/// An iterator over elements in descending order, which pops them one by
+/// one off a [`LeonardoHeap`]: taking the first `k` of `n` elements costs
+/// `O(n + k log n)`.
+#[derive(Clone, Debug)]
+pub struct SortedLazy<T> {
+    heap : LeonardoHeap<T>,
+}
+
+impl<T> Iterator for SortedLazy<T>
+where T: PartialOrd {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.heap.pop_max() }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for SortedLazy<T>
+where T: PartialOrd {}
+
+/// Adds [`sorted_lazy`](Self::sorted_lazy) to every iterator.
+pub trait SortedLazyExt: Iterator {
+    /// Collects the elements into a [`LeonardoHeap`] in `O(n)`, and yields
+    /// them in descending order, the greatest first, as they are asked for.
+    fn sorted_lazy(self) -> SortedLazy<Self::Item>
+    where Self: Sized, Self::Item: PartialOrd {
+        SortedLazy { heap: LeonardoHeap::from_vec(self.collect()) }
+    }
+}
+
+impl<I> SortedLazyExt for I
+where I: Iterator {}
+//}}}
@@ -0,0 +1,159 @@
+//{{{ Struct `LeonardoHeap`
+use macro_leon::gen_leonardo_ind;
+use crate::hole::Hole;
+
+const LEON: &[usize] = &gen_leonardo_ind!();
+
+// Sifts the element of `hole` down the Leonardo tree of order `order` whose
+// root is at the hole.
+fn sift<T, F>(hole: &mut Hole<T>, mut order: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    while order >= 2 {
+        let ind_r = hole.pos() - 1;
+        let ind_l = ind_r - LEON[order - 2];
+        let ind_c = if is_less(hole.get(ind_r), hole.get(ind_l)) {
+            order -= 1;
+            ind_l
+        } else {
+            order -= 2;
+            ind_r
+        };
+        if !is_less(hole.element(), hole.get(ind_c)) { break; }
+        hole.move_to(ind_c);
+    }
+}
+
+// Moves the root of tree `t` of the forest `orders` on `data`, rooted at
+// `root`, to its place among the roots of trees `0 ..= t`, then sifts it
+// down the tree it lands in. The trees are heaps and the roots before `t`
+// ascend.
+fn trinkle<T, F>(data: &mut [T], orders: &[usize], mut t: usize, root: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let mut hole = Hole::new(data, root);
+    while t > 0 {
+        let order = orders[t];
+        let prev = hole.pos() - LEON[order];
+        if !is_less(hole.element(), hole.get(prev)) { break; }
+        if order >= 2 {
+            let ind_r = hole.pos() - 1;
+            let ind_l = ind_r - LEON[order - 2];
+            if is_less(hole.get(prev), hole.get(ind_r)) ||
+               is_less(hole.get(prev), hole.get(ind_l)) { break; }
+        }
+        hole.move_to(prev);
+        t -= 1;
+    }
+    sift(&mut hole, orders[t], is_less);
+}
+
+// Adds a new last element to the forest `orders`, as the root of the last
+// two trees if their orders are consecutive, or as a tree of its own.
+fn grow(orders: &mut Vec<usize>) {
+    match orders[..] {
+        [.., k1, k0] if k1 == k0 + 1 => {
+            orders.truncate(orders.len() - 2);
+            orders.push(k1 + 1);
+        },
+        [.., 1] => orders.push(0),
+        _ => orders.push(1),
+    }
+}
+
+// Takes the last tree, whose root has left `data`, apart into its subtrees
+// and rectifies their roots.
+fn shrink<T, F>(data: &mut [T], orders: &mut Vec<usize>, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let order = orders.pop().unwrap();
+    if order < 2 { return; }
+    let n = data.len();
+    orders.push(order - 1);
+    trinkle(data, orders, orders.len() - 1, n - 1 - LEON[order - 2], is_less);
+    orders.push(order - 2);
+    trinkle(data, orders, orders.len() - 1, n - 1, is_less);
+}
+
+/// A max priority queue on a forest of Leonardo heaps, the structure behind
+/// [`smooth_l_sort`](super::smooth_l_sort).
+///
+/// The elements are stored in a vector as a sequence of heap ordered
+/// Leonardo trees, each rooted at its last element, with roots ascending
+/// from left to right: the greatest element is the last one. Pushing and
+/// popping cost `O(log n)` comparisons, but only `O(1)` while the elements
+/// come (or are popped) in nearly ascending order, so that
+/// [`from_vec`](Self::from_vec) followed by
+/// [`into_sorted_vec`](Self::into_sorted_vec) sorts in `O(n)` on nearly
+/// sorted input, which a binary heap cannot.
+#[derive(Clone, Debug)]
+pub struct LeonardoHeap<T> {
+    data   : Vec<T>,
+    // Orders of the trees from left to right, strictly descending.
+    orders : Vec<usize>,
+}
+
+impl<T> Default for LeonardoHeap<T> {
+    fn default() -> Self {
+        LeonardoHeap { data: vec![], orders: vec![] }
+    }
+}
+
+impl<T> LeonardoHeap<T>
+where T: PartialOrd {
+    /// Creates an empty heap.
+    pub fn new() -> Self { Self::default() }
+
+    /// Builds a heap from the elements of `data` in `O(n)`.
+    ///
+    /// Each tree is heap ordered as soon as it is complete, in time bounded by
+    /// its height, and the `O(log n)` roots are put in order at the end.
+    pub fn from_vec(mut data: Vec<T>) -> Self {
+        let mut orders = vec![];
+        for ind in 0 .. data.len() {
+            grow(&mut orders);
+            sift(&mut Hole::new(&mut data[..= ind], ind), *orders.last().unwrap(), &mut T::lt);
+        }
+        let mut root = 0;
+        for t in 0 .. orders.len() {
+            root += LEON[orders[t]];
+            trinkle(&mut data[.. root], &orders, t, root - 1, &mut T::lt);
+        }
+        LeonardoHeap { data, orders }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize { self.data.len() }
+
+    /// Whether the heap is empty.
+    pub fn is_empty(&self) -> bool { self.data.is_empty() }
+
+    /// The greatest element, if any.
+    pub fn peek(&self) -> Option<&T> { self.data.last() }
+
+    /// Adds `x` to the heap.
+    pub fn push(&mut self, x: T) {
+        self.data.push(x);
+        grow(&mut self.orders);
+        let n = self.data.len();
+        trinkle(&mut self.data, &self.orders, self.orders.len() - 1, n - 1, &mut T::lt);
+    }
+
+    /// Removes the greatest element and returns it, if any.
+    pub fn pop_max(&mut self) -> Option<T> {
+        let x = self.data.pop()?;
+        shrink(&mut self.data, &mut self.orders, &mut T::lt);
+        Some(x)
+    }
+
+    /// The elements in ascending order, popped in place.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for n in (1 .. self.data.len()).rev() {
+            shrink(&mut self.data[.. n], &mut self.orders, &mut T::lt);
+        }
+        self.data
+    }
+}
+
+impl<T> From<Vec<T>> for LeonardoHeap<T>
+where T: PartialOrd {
+    fn from(data: Vec<T>) -> Self { Self::from_vec(data) }
+}
+//}}}
@@ -0,0 +1,24 @@
+//! Heap based sorts: binary and `d`-ary heap sort, weak heap sort and smooth
+//! sort, and partial sort and top `k` selection on a binary heap.
+//!
+//! The Leonardo heap of smooth sort, the weak heap of weak heap sort and the
+//! `d`-ary heap are also priority queues, and the Leonardo heap sorts a
+//! stream online.
+mod smooth;
+mod leonardo;
+mod incremental;
+mod weak_heap;
+mod heap;
+mod dary;
+mod partial;
+pub use smooth::{smooth_b_sort, smooth_b_sort_by, smooth_b_sort_by_key};
+pub use smooth::{smooth_l_sort, smooth_l_sort_by, smooth_l_sort_by_key};
+pub use leonardo::LeonardoHeap;
+pub use incremental::{IncrementalSorter, SortedLazy, SortedLazyExt};
+pub use weak_heap::WeakHeap;
+pub use weak_heap::{weak_heap_sort, weak_heap_sort_by, weak_heap_sort_by_key};
+pub use heap::{heap_sort, heap_sort_by, heap_sort_by_key};
+pub use dary::{dary_heap_sort, dary_heap_sort_by, dary_heap_sort_by_key, DaryHeap};
+pub use partial::{partial_sort, partial_sort_by, partial_sort_by_key};
+pub use partial::{top_k, top_k_by, top_k_by_key};
+pub(crate) use heap::heap_sort_impl;
@@ -0,0 +1,96 @@
+//{{{ Partial sort
+use std::cmp::Ordering;
+use super::heap::{sift_up, sift_down, sort_heap};
+
+fn partial_sort_impl<T, F>(data: &mut [T], k: usize, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let k = k.min(data.len());
+    if k == 0 { return; }
+    let (heap, rest) = data.split_at_mut(k);
+    for ind in (0 .. k / 2).rev() {
+        sift_down(heap, ind, is_less);
+    }
+    for x in rest.iter_mut() {
+        if is_less(x, &heap[0]) {
+            std::mem::swap(x, &mut heap[0]);
+            sift_down(heap, 0, is_less);
+        }
+    }
+    sort_heap(heap, is_less);
+}
+
+/// Moves the `k` least elements of `data` to `data[.. k]` in ascending order,
+/// leaving the others in `data[k ..]` in unspecified order.
+///
+/// Keeps a binary max heap of the least elements seen so far in `data[.. k]`,
+/// and sorts it with heap sort at the end, in `O(n log k)`. Sorts all of
+/// `data` if `k >= data.len()`.
+pub fn partial_sort<T>(data: &mut [T], k: usize)
+where T: PartialOrd {
+    partial_sort_impl(data, k, &mut T::lt);
+}
+
+/// Moves the `k` least elements of `data` to its front according to
+/// `compare`.
+pub fn partial_sort_by<T, F>(data: &mut [T], k: usize, mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    partial_sort_impl(data, k, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Moves the `k` least elements of `data` to its front by the key extracted
+/// with `f`.
+pub fn partial_sort_by_key<T, K, F>(data: &mut [T], k: usize, mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    partial_sort_impl(data, k, &mut |a, b| f(a) < f(b));
+}
+//}}}
+//{{{ Top k
+fn top_k_impl<T, I, F>(iter: I, k: usize, is_less: &mut F) -> Vec<T>
+where I: IntoIterator<Item = T>, F: FnMut(&T, &T) -> bool {
+    // A max heap for the reversed order keeps the least of the greatest
+    // elements seen so far at its root.
+    let is_greater = &mut |a: &T, b: &T| is_less(b, a);
+    if k == 0 { return vec![]; }
+    // `k` may exceed the number of items by far: reserve no more than the
+    // items `iter` announces, and let the heap grow past them if need be.
+    let iter = iter.into_iter();
+    let mut heap = Vec::with_capacity(k.min(iter.size_hint().0));
+    for x in iter {
+        if heap.len() < k {
+            let ind = heap.len();
+            heap.push(x);
+            sift_up(&mut heap, ind, is_greater);
+        } else if is_greater(&x, &heap[0]) {
+            heap[0] = x;
+            sift_down(&mut heap, 0, is_greater);
+        }
+    }
+    sort_heap(&mut heap, is_greater);
+    heap
+}
+
+/// Returns the `k` greatest items of `iter` in descending order, or all of
+/// them if there are fewer.
+///
+/// Consumes `iter` one item at a time, keeping only the greatest items seen
+/// so far in a binary heap of size `k`, in `O(n log k)` time and `O(k)`
+/// space.
+pub fn top_k<T, I>(iter: I, k: usize) -> Vec<T>
+where T: PartialOrd, I: IntoIterator<Item = T> {
+    top_k_impl(iter, k, &mut T::lt)
+}
+
+/// Returns the `k` greatest items of `iter` in descending order according to
+/// `compare`.
+pub fn top_k_by<T, I, F>(iter: I, k: usize, mut compare: F) -> Vec<T>
+where I: IntoIterator<Item = T>, F: FnMut(&T, &T) -> Ordering {
+    top_k_impl(iter, k, &mut |a, b| compare(a, b) == Ordering::Less)
+}
+
+/// Returns the `k` greatest items of `iter` in descending order by the key
+/// extracted with `f`.
+pub fn top_k_by_key<T, I, K, F>(iter: I, k: usize, mut f: F) -> Vec<T>
+where I: IntoIterator<Item = T>, K: PartialOrd, F: FnMut(&T) -> K {
+    top_k_impl(iter, k, &mut |a, b| f(a) < f(b))
+}
+//}}}
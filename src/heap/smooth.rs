@@ -0,0 +1,257 @@
+use std::cmp::Ordering;
+use crate::hole::Hole;
+//{{{ Smooth sort (based on binary heap)
+fn smooth_b_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    fn heap_rectify<T, F>(mut data: &mut [T], mut depth: u32, mut flag: Option<&[bool]>,
+                          is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        let mut ind;
+        let mut delta;
+        'out: loop {
+            let n = data.len();
+            if n <= 1 { return; }
+            ind = n - 1;
+            let ind_r = ind - 1;
+            delta = if depth > 0 {
+                (1usize << depth - 1) - 1
+            } else { 0 };
+            let ind_l = ind_r - delta;
+            match flag {
+                Some(flg) if ind_l >= delta && {
+                    let v = &data[ind_l - delta];
+                    is_less(&data[ind], v) && (delta == 0 ||
+                        is_less(&data[ind_l], v) && is_less(&data[ind_r], v))
+                } => if flg[0] {
+                    for (i, &fl) in flg.iter().enumerate().skip(1) {
+                        if fl {
+                            data.swap(ind_l - delta, ind);
+                            data = &mut data[..= ind_l - delta];
+                            depth += i as u32;
+                            flag = Some(&flg[i ..]);
+                            continue 'out;
+                        }
+                    }
+                    break;
+                } else {
+                    data.swap(ind_l - delta, ind);
+                    data = &mut data[..= ind_l - delta];
+                    flag = Some(&flg[1 ..]);
+                    continue;
+                },
+                _ => break,
+            }
+        }
+        let mut hole = Hole::new(data, ind);
+        while delta > 0 {
+            let mut ind_s = hole.pos() - 1;
+            let ind_l = ind_s - delta;
+            if is_less(hole.get(ind_s), hole.get(ind_l)) {
+                ind_s = ind_l;
+            }
+            if is_less(hole.element(), hole.get(ind_s)) {
+                hole.move_to(ind_s);
+                delta >>= 1;
+            } else { break; }
+        }
+    }
+    let n = data.len();
+    let mut flag = [false; 1 << std::mem::size_of::<usize>()];
+    let mut last_bit = 0usize;
+    let mut m_bit = 0usize;
+    for i in 0 .. n {
+        if !flag[0] {
+            flag[last_bit] = false;
+            last_bit += 1;
+            if !flag[last_bit] {
+                (flag[0], flag[last_bit]) = (true, true);
+            }
+        } else if !flag[1] {
+            flag[1] = true;
+            last_bit = 1;
+        } else {
+            flag[0] = false;
+        }
+        heap_rectify(&mut data[..= i], last_bit as u32,
+            if last_bit > m_bit {
+                m_bit = last_bit;
+                if n - i <= 1 << m_bit as u32 {
+                    m_bit = 0;
+                    if flag[0] {
+                        Some(&flag[last_bit ..])
+                    } else {
+                        Some(&flag[last_bit - 1 ..])
+                    }
+                } else { None }
+            } else { None },
+            is_less);
+    }
+    for i in (1 .. n).rev() {
+        if last_bit > 1 {
+            if flag[0] {
+                flag[0] = false;
+                flag[last_bit] = false;
+            }
+            last_bit -= 1;
+            flag[last_bit] = true;
+            heap_rectify(&mut data[..= i - (1usize << last_bit)],
+                last_bit as u32, Some(&flag[last_bit ..]), is_less);
+            heap_rectify(&mut data[.. i],
+                last_bit as u32, Some(&flag[last_bit - 1 ..]), is_less);
+        } else if flag[0] {
+            for (i, &fl) in flag.iter().enumerate().skip(2) {
+                if fl {
+                    last_bit = i;
+                    break;
+                }
+            }
+            flag[1] = false;
+        } else {
+            flag[0] = true;
+        }
+    }
+}
+
+/// Sorts `data` with smooth sort built on a forest of perfect binary heaps.
+pub fn smooth_b_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    smooth_b_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with smooth sort (binary heap) according to `compare`.
+pub fn smooth_b_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    smooth_b_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with smooth sort (binary heap) by the key extracted with `f`.
+pub fn smooth_b_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    smooth_b_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
+//{{{ Smooth sort (based on Leonardo heap)
+fn smooth_l_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    use macro_leon::gen_leonardo_ind;
+    const LEON: &[usize] = &gen_leonardo_ind!();
+    fn heap_rectify<T, F>(mut data: &mut [T], mut depth: usize, mut flag: Option<&[bool]>,
+                          is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool {
+        let mut ind;
+        'out: loop {
+            let n = data.len();
+            if n <= 1 { return; }
+            ind = n - 1;
+            let ind_r = ind - 1;
+            let (delta_r, delta_l) = if depth >= 2 {
+                (LEON[depth - 2], LEON[depth - 1])
+            } else { (0, 0) };
+            let ind_l = ind_r - delta_r;
+            match flag {
+                Some(flg) if ind_l >= delta_l && {
+                    let v = &data[ind_l - delta_l];
+                    is_less(&data[ind], v) && (delta_l == 0 ||
+                        is_less(&data[ind_l], v) && is_less(&data[ind_r], v))
+                } => {
+                    for (i, &fl) in flg.iter().enumerate().skip(1) {
+                        if fl {
+                            data.swap(ind_l - delta_l, ind);
+                            data = &mut data[..= ind_l - delta_l];
+                            depth += i;
+                            flag = Some(&flg[i ..]);
+                            continue 'out;
+                        }
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+        let mut hole = Hole::new(data, ind);
+        while depth >= 2 {
+            let mut ind_s = hole.pos() - 1;
+            depth -= 1;
+            let ind_l = ind_s - LEON[depth - 1];
+            if is_less(hole.get(ind_s), hole.get(ind_l)) {
+                ind_s = ind_l;
+            } else {
+                depth -= 1;
+            }
+            if is_less(hole.element(), hole.get(ind_s)) {
+                hole.move_to(ind_s);
+            } else { break; }
+        }
+    }
+    let n = data.len();
+    let mut flag = [false; LEON.len() + 1];
+    let mut last_bit = 2usize;
+    let mut m_bit = 0usize;
+    for i in 0 .. n {
+        if flag[last_bit + 1] {
+            (flag[last_bit], flag[last_bit + 1]) = (false, false);
+            last_bit += 2;
+        } else if last_bit >= 2 {
+            last_bit = 1;
+        } else {
+            last_bit = 0;
+        }
+        flag[last_bit] = true;
+        heap_rectify(&mut data[..= i], last_bit,
+            if last_bit > m_bit {
+                m_bit = last_bit;
+                if n - i <= LEON[m_bit - 1] + 1 {
+                    m_bit = 0;
+                    Some(&flag[last_bit ..])
+                } else { None }
+            } else if m_bit == 0 && last_bit == 0 && n - i <= 1 {
+                Some(&flag[..])
+            } else { None },
+            is_less);
+    }
+    for i in (1 .. n).rev() {
+        if last_bit >= 2 {
+            flag[last_bit] = false;
+            last_bit -= 1;
+            flag[last_bit] = true;
+            heap_rectify(&mut data[.. i - LEON[last_bit - 1]],
+                last_bit, Some(&flag[last_bit ..]), is_less);
+            last_bit -= 1;
+            flag[last_bit] = true;
+            heap_rectify(&mut data[.. i],
+                last_bit, Some(&flag[last_bit ..]), is_less);
+        } else if last_bit != 0 {
+            for (i, &fl) in flag.iter().enumerate().skip(2) {
+                if fl {
+                    last_bit = i;
+                    break;
+                }
+            }
+            flag[1] = false;
+        } else {
+            last_bit = 1;
+            flag[0] = false;
+        }
+    }
+}
+
+/// Sorts `data` with smooth sort built on a forest of Leonardo heaps.
+///
+/// Runs in `O(n)` on sorted input and `O(n log n)` in the worst case.
+pub fn smooth_l_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    smooth_l_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with smooth sort (Leonardo heap) according to `compare`.
+pub fn smooth_l_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    smooth_l_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with smooth sort (Leonardo heap) by the key extracted with `f`.
+pub fn smooth_l_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    smooth_l_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
@@ -0,0 +1,194 @@
+//{{{ Weak heap
+use std::cmp::Ordering;
+
+// The reverse bits of a weak heap, packed 64 to a word. Node `i` has the
+// left child `2 i + r(i)` and the right child `2 i + 1 - r(i)`; the root
+// has the right child `1` only.
+#[derive(Clone, Debug, Default)]
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn new(n: usize) -> Self { Bits(vec![0; n.div_ceil(64)]) }
+
+    fn get(&self, ind: usize) -> usize { (self.0[ind >> 6] >> (ind & 63)) as usize & 1 }
+
+    fn flip(&mut self, ind: usize) { self.0[ind >> 6] ^= 1 << (ind & 63); }
+
+    // Makes room for node `ind`, with a clear bit.
+    fn clear(&mut self, ind: usize) {
+        if ind >> 6 == self.0.len() { self.0.push(0); }
+        self.0[ind >> 6] &= !(1 << (ind & 63));
+    }
+}
+
+// The distinguished ancestor of node `ind > 0`: the parent of the first
+// node on the way up which is a right child.
+fn ancestor(bits: &Bits, mut ind: usize) -> usize {
+    while ind & 1 == bits.get(ind >> 1) { ind >>= 1; }
+    ind >> 1
+}
+
+// Joins the weak heap rooted at `ind` to its distinguished ancestor `anc`:
+// if `data[anc]` is less, swaps both and the subtrees of `ind`. Returns
+// whether it swapped.
+fn join<T, F>(data: &mut [T], bits: &mut Bits, anc: usize, ind: usize, is_less: &mut F) -> bool
+where F: FnMut(&T, &T) -> bool {
+    if !is_less(&data[anc], &data[ind]) { return false; }
+    data.swap(anc, ind);
+    bits.flip(ind);
+    true
+}
+
+// Builds a weak heap on `data` with `n - 1` comparisons.
+fn heapify<T, F>(data: &mut [T], bits: &mut Bits, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    for ind in (1 .. data.len()).rev() {
+        join(data, bits, ancestor(bits, ind), ind, is_less);
+    }
+}
+
+// Moves the last node of `data` up to its place.
+fn sift_up<T, F>(data: &mut [T], bits: &mut Bits, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let mut ind = data.len() - 1;
+    while ind != 0 {
+        let anc = ancestor(bits, ind);
+        if !join(data, bits, anc, ind, is_less) { break; }
+        ind = anc;
+    }
+}
+
+// Restores the weak heap on `data` after its root changed: joins the root
+// with every node of the left spine of its right child, bottom up, with at
+// most `⌈log n⌉` comparisons.
+fn sift_down<T, F>(data: &mut [T], bits: &mut Bits, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let n = data.len();
+    if n <= 1 { return; }
+    let mut ind = 1;
+    while 2 * ind + bits.get(ind) < n { ind = 2 * ind + bits.get(ind); }
+    while ind != 0 {
+        join(data, bits, 0, ind, is_less);
+        ind >>= 1;
+    }
+}
+
+// Sorts the weak heap `data` by moving its root to the back repeatedly.
+fn sort_heap<T, F>(data: &mut [T], bits: &mut Bits, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    for n in (1 .. data.len()).rev() {
+        data.swap(0, n);
+        sift_down(&mut data[.. n], bits, is_less);
+    }
+}
+
+/// A max priority queue on a weak heap: a binary tree in which every node
+/// is not less than the nodes of its right subtree, and the root has no
+/// left subtree.
+///
+/// Which child of a node is its left one is given by one reverse bit per
+/// node, packed in words, so that a join of two subtrees swaps them by
+/// flipping a bit. Building the heap of `n` elements takes `n - 1`
+/// comparisons, popping at most `⌈log n⌉`, and pushing `O(log n)`, `O(1)`
+/// on average.
+#[derive(Clone, Debug)]
+pub struct WeakHeap<T> {
+    data : Vec<T>,
+    bits : Bits,
+}
+
+impl<T> Default for WeakHeap<T> {
+    fn default() -> Self {
+        WeakHeap { data: vec![], bits: Bits::default() }
+    }
+}
+
+impl<T> WeakHeap<T>
+where T: PartialOrd {
+    /// Creates an empty heap.
+    pub fn new() -> Self { Self::default() }
+
+    /// Builds a heap from the elements of `data` with `n - 1` comparisons.
+    pub fn from_vec(mut data: Vec<T>) -> Self {
+        let mut bits = Bits::new(data.len());
+        heapify(&mut data, &mut bits, &mut T::lt);
+        WeakHeap { data, bits }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize { self.data.len() }
+
+    /// Whether the heap is empty.
+    pub fn is_empty(&self) -> bool { self.data.is_empty() }
+
+    /// The greatest element, if any.
+    pub fn peek(&self) -> Option<&T> { self.data.first() }
+
+    /// Adds `x` to the heap.
+    pub fn push(&mut self, x: T) {
+        let ind = self.data.len();
+        self.data.push(x);
+        self.bits.clear(ind);
+        // A new left child of its parent, so that the parent keeps no
+        // right subtree to dominate.
+        if ind & 1 == 0 && ind != 0 { self.bits.clear(ind >> 1); }
+        sift_up(&mut self.data, &mut self.bits, &mut T::lt);
+    }
+
+    /// Removes the greatest element and returns it, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.data.len().checked_sub(1)?;
+        self.data.swap(0, last);
+        let x = self.data.pop();
+        sift_down(&mut self.data, &mut self.bits, &mut T::lt);
+        x
+    }
+
+    /// Moves all elements of `other` into the heap, pushing those of the
+    /// smaller of both into the larger one.
+    pub fn merge(&mut self, mut other: WeakHeap<T>) {
+        if other.len() > self.len() { std::mem::swap(self, &mut other); }
+        for x in other.data { self.push(x); }
+    }
+
+    /// The elements in ascending order, popped in place.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        sort_heap(&mut self.data, &mut self.bits, &mut T::lt);
+        self.data
+    }
+}
+
+impl<T> From<Vec<T>> for WeakHeap<T>
+where T: PartialOrd {
+    fn from(data: Vec<T>) -> Self { Self::from_vec(data) }
+}
+//}}}
+//{{{ Weak heap sort
+fn weak_heap_sort_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    // The same two phases as `WeakHeap::from_vec` and
+    // `WeakHeap::into_sorted_vec`, on a borrowed slice.
+    let mut bits = Bits::new(data.len());
+    heapify(data, &mut bits, is_less);
+    sort_heap(data, &mut bits, is_less);
+}
+
+/// Sorts `data` with weak heap sort, in at most
+/// `n ⌈log n⌉ - 2^⌈log n⌉ + n - 1` comparisons.
+pub fn weak_heap_sort<T>(data: &mut [T])
+where T: PartialOrd {
+    weak_heap_sort_impl(data, &mut T::lt);
+}
+
+/// Sorts `data` with weak heap sort according to `compare`.
+pub fn weak_heap_sort_by<T, F>(data: &mut [T], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    weak_heap_sort_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts `data` with weak heap sort by the key extracted with `f`.
+pub fn weak_heap_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    weak_heap_sort_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
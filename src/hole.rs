@@ -0,0 +1,54 @@
+//{{{ Struct `Hole`
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+/// An element taken out of a slice, leaving a hole behind it.
+///
+/// Sifting the element along a path of the slice then costs one move per step
+/// instead of a swap. The element is written back into the hole on drop, so
+/// the slice stays a permutation of the original one even if a comparison
+/// panics in the middle of the sift.
+pub(crate) struct Hole<'a, T> {
+    data : &'a mut [T],
+    elt  : ManuallyDrop<T>,
+    pos  : usize,
+}
+
+impl<'a, T> Hole<'a, T> {
+    /// Takes the element at `pos` out of `data`.
+    pub(crate) fn new(data: &'a mut [T], pos: usize) -> Self {
+        let elt = unsafe { ptr::read(&data[pos]) };
+        Hole { data, elt: ManuallyDrop::new(elt), pos }
+    }
+
+    pub(crate) fn pos(&self) -> usize { self.pos }
+
+    /// The element taken out of the slice.
+    pub(crate) fn element(&self) -> &T { &self.elt }
+
+    /// The element at `ind`, which must not be the hole.
+    pub(crate) fn get(&self, ind: usize) -> &T {
+        assert!(ind != self.pos);
+        &self.data[ind]
+    }
+
+    /// Moves the element at `ind` into the hole, leaving the hole at `ind`.
+    pub(crate) fn move_to(&mut self, ind: usize) {
+        assert!(ind != self.pos && ind < self.data.len());
+        unsafe {
+            let ptr = self.data.as_mut_ptr();
+            ptr::copy_nonoverlapping(ptr.add(ind), ptr.add(self.pos), 1);
+        }
+        self.pos = ind;
+    }
+}
+
+impl<T> Drop for Hole<'_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            let ptr = self.data.as_mut_ptr();
+            ptr::copy_nonoverlapping(&*self.elt, ptr.add(self.pos), 1);
+        }
+    }
+}
+//}}}
@@ -0,0 +1,30 @@
+//! Several sort algorithms in rust language.
+//!
+//! * [`bisection`]: quick sort, intro sort, pattern-defeating quick sort,
+//!   merge sort and tim sort; selection of the `k`-th element
+//! * [`heap`]: heap sort (binary and `d`-ary), weak heap sort and smooth sort
+//!   (based on binary/Leonardo heap); partial sort and top `k`; Leonardo
+//!   heap, weak heap and `d`-ary heap priority queues; online sort of a stream
+//! * [`bitonic`]: bitonic sort (recursion/iteration, serial/parallel), also of
+//!   key-value pairs, and argsort; sample sort (parallel)
+//! * [`radix`]: LSD radix sort and MSD radix sort on integer and float keys
+//! * [`small`]: optimal or best known sorting networks on up to 16 elements
+//!
+//! Every function sorts a slice in ascending order in place. The [`Sorter`]
+//! trait describes each algorithm, [`sorter::registry`] lists the comparison
+//! sorts and [`sorter::radix_registry`] the radix sorts.
+//!
+//! The parallel sorts run on their own threads, or on rayon's thread pool
+//! with the `rayon` feature; see [`pool`].
+//!
+//! Only merge sort, tim sort and LSD radix sort are stable, i.e. keep equal
+//! elements in their original order; [`Sorter::stable`] tells them apart.
+pub mod bitonic;
+pub mod heap;
+pub mod bisection;
+pub mod radix;
+pub mod sorter;
+pub mod pool;
+pub mod small;
+pub use sorter::Sorter;
+mod hole;
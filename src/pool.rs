@@ -0,0 +1,190 @@
+//! Threads for the parallel sorts: a pool of persistent worker threads, and
+//! the fork-join primitive of the recursive parallel sorts.
+//!
+//! With the `rayon` feature, forks run on the rayon pool of the calling
+//! thread: the global pool, or the one entered with
+//! `rayon::ThreadPool::install`.
+//{{{ Struct `Pool`
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Condvar, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+// A job borrowed by `Pool::run`, with its lifetime erased.
+type Job = &'static (dyn Fn(usize) + Sync);
+
+struct Channels {
+    jobs : Vec<mpsc::Sender<Job>>,
+    done : mpsc::Receiver<thread::Result<()>>,
+}
+
+/// A fixed set of worker threads, which wait for jobs until the pool is
+/// dropped.
+///
+/// Parallel sorts given a pool run on its threads instead of spawning their
+/// own, so a pool created once can serve any number of sorts.
+pub struct Pool {
+    channels : Mutex<Channels>,
+    handles  : Vec<JoinHandle<()>>,
+}
+
+impl Pool {
+    /// Starts a pool of `threads` workers.
+    ///
+    /// # Panics
+    /// Panics if `threads` is zero.
+    pub fn new(threads: usize) -> Self {
+        assert!(threads > 0);
+        let (done_tx, done) = mpsc::channel();
+        let mut jobs = vec![];
+        let mut handles = vec![];
+        for ind in 0 .. threads {
+            let (job_tx, job_rx) = mpsc::channel::<Job>();
+            let done_tx = done_tx.clone();
+            handles.push(thread::spawn(move || {
+                for job in job_rx {
+                    let ret = panic::catch_unwind(AssertUnwindSafe(|| job(ind)));
+                    if done_tx.send(ret).is_err() { break; }
+                }
+            }));
+            jobs.push(job_tx);
+        }
+        Pool { channels: Mutex::new(Channels { jobs, done }), handles }
+    }
+
+    /// Number of worker threads.
+    pub fn threads(&self) -> usize { self.handles.len() }
+
+    /// Runs `job(ind)` on the worker `ind` for every worker and returns once
+    /// all of them are done. If `job` panicked on some worker, the panic is
+    /// resumed on the calling thread afterwards.
+    ///
+    /// Calls from several threads are run one after another. Calling `run`
+    /// from inside a job of the same pool deadlocks.
+    pub fn run<F>(&self, job: &F)
+    where F: Fn(usize) + Sync {
+        let channels = self.channels.lock().unwrap_or_else(PoisonError::into_inner);
+        let job: &(dyn Fn(usize) + Sync) = job;
+        // SAFETY: a worker only touches `job` before reporting it done, and
+        // every worker which received `job` has reported it done before this
+        // function returns or unwinds, so `job` outlives all of its uses.
+        let job = unsafe { mem::transmute::<&(dyn Fn(usize) + Sync), Job>(job) };
+        let sent = channels.jobs.iter().filter(|tx| tx.send(job).is_ok()).count();
+        let mut payload = None;
+        for _ in 0 .. sent {
+            match channels.done.recv() {
+                Ok(Ok(())) => {}
+                Ok(Err(p)) => { payload.get_or_insert(p); }
+                // All workers are gone, so none of them holds `job` anymore.
+                Err(_) => break,
+            }
+        }
+        drop(channels);
+        if let Some(p) = payload { panic::resume_unwind(p); }
+    }
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        self.channels.get_mut().unwrap_or_else(PoisonError::into_inner).jobs.clear();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+//}}}
+//{{{ Scoped threads
+/// Runs `job(ind)` for every `ind` below `threads` on as many scoped
+/// threads, like [`Pool::run`] on a pool started for this call only.
+pub(crate) fn run_scoped<F>(threads: usize, job: &F)
+where F: Fn(usize) + Sync {
+    let payload = thread::scope(|s| {
+        let handles = (0 .. threads).map(|ind| s.spawn(move || job(ind))).collect::<Vec<_>>();
+        let mut payload = None;
+        for handle in handles {
+            if let Err(p) = handle.join() { payload.get_or_insert(p); }
+        }
+        payload
+    });
+    if let Some(p) = payload { panic::resume_unwind(p); }
+}
+/// Runs `a` and `b` in parallel and returns both results, resuming a panic
+/// of either one once both are done. `b` runs on a scoped thread, or is
+/// offered to the rayon pool with the `rayon` feature.
+pub(crate) fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where A: FnOnce() -> RA + Send, B: FnOnce() -> RB + Send, RA: Send, RB: Send {
+    #[cfg(feature = "rayon")]
+    { rayon::join(a, b) }
+    #[cfg(not(feature = "rayon"))]
+    thread::scope(|s| {
+        let handle = s.spawn(b);
+        let ra = a();
+        match handle.join() {
+            Ok(rb) => (ra, rb),
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    })
+}
+
+/// Runs `f` on every item of `items` in parallel, forking with [`join`].
+pub(crate) fn for_each<I, F>(mut items: Vec<I>, f: &F)
+where I: Send, F: Fn(I) + Sync {
+    if items.len() <= 1 {
+        items.into_iter().for_each(f);
+        return;
+    }
+    let items2 = items.split_off(items.len() / 2);
+    join(|| for_each(items, f), || for_each(items2, f));
+}
+//}}}
+//{{{ Struct `Barrier`
+/// A reusable barrier for a fixed number of threads, which can be poisoned to
+/// release every waiting thread, e.g. when one of them panicked.
+pub(crate) struct Barrier {
+    n     : usize,
+    // Threads arrived in the current generation, generation, poisoned.
+    state : Mutex<(usize, usize, bool)>,
+    cvar  : Condvar,
+}
+
+impl Barrier {
+    pub(crate) fn new(n: usize) -> Self {
+        Barrier { n, state: Mutex::new((0, 0, false)), cvar: Condvar::new() }
+    }
+
+    /// Blocks until all `n` threads have called `wait`. Returns `false` if
+    /// the barrier is poisoned.
+    pub(crate) fn wait(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.2 { return false; }
+        let generation = state.1;
+        state.0 += 1;
+        if state.0 == self.n {
+            (state.0, state.1) = (0, generation.wrapping_add(1));
+            self.cvar.notify_all();
+            return true;
+        }
+        while state.1 == generation && !state.2 {
+            state = self.cvar.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        state.1 != generation
+    }
+
+    /// Returns a guard which poisons the barrier if dropped while panicking.
+    pub(crate) fn poison_on_panic(&self) -> PoisonOnPanic<'_> {
+        PoisonOnPanic(self)
+    }
+}
+
+pub(crate) struct PoisonOnPanic<'a>(&'a Barrier);
+
+impl Drop for PoisonOnPanic<'_> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            let mut state = self.0.state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.2 = true;
+            self.0.cvar.notify_all();
+        }
+    }
+}
+//}}}
@@ -0,0 +1,62 @@
+//{{{ LSD radix sort
+use std::mem;
+use super::{RadixKey, apply_permutation};
+
+fn lsd_radix_sort_impl<T, K, F>(data: &mut [T], key: F)
+where T: Copy, K: RadixKey, F: Fn(&T) -> K {
+    let n = data.len();
+    if n < 2 { return; }
+    let mut counts = vec![[0usize; 256]; K::BYTES];
+    for x in data.iter() {
+        let k = key(x);
+        for (ind, count) in counts.iter_mut().enumerate() {
+            count[k.byte(ind) as usize] += 1;
+        }
+    }
+    let mut buf = data.to_vec();
+    let (mut src, mut dest): (&mut [T], &mut [T]) = (data, &mut buf);
+    let mut odd = false;
+    for (ind, count) in counts.iter().enumerate() {
+        // Every key has the same byte here, so the pass would change nothing.
+        if count.contains(&n) { continue; }
+        let mut offset = [0usize; 256];
+        let mut sum = 0;
+        for (off, &c) in offset.iter_mut().zip(count) {
+            *off = sum;
+            sum += c;
+        }
+        for x in src.iter() {
+            let b = key(x).byte(ind) as usize;
+            dest[offset[b]] = *x;
+            offset[b] += 1;
+        }
+        mem::swap(&mut src, &mut dest);
+        odd = !odd;
+    }
+    if odd { dest.copy_from_slice(src); }
+}
+
+/// Sorts `data` with LSD radix sort.
+///
+/// Makes one stable counting pass per byte of the key, from the least
+/// significant byte up, scattering between `data` and a scratch buffer of
+/// the same length. Passes on a byte shared by all keys are skipped. Runs in
+/// `O(n)` for keys of fixed width.
+pub fn lsd_radix_sort<T>(data: &mut [T])
+where T: RadixKey {
+    lsd_radix_sort_impl(data, |x| *x);
+}
+
+/// Sorts `data` with LSD radix sort by the key extracted with `f`.
+///
+/// The keys are extracted once, sorted along with the original indices,
+/// and the resulting permutation is applied to `data` with swaps. The sort
+/// is stable.
+pub fn lsd_radix_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: RadixKey, F: FnMut(&T) -> K {
+    let mut keys = data.iter().enumerate().map(|(ind, x)| (f(x), ind)).collect::<Vec<_>>();
+    lsd_radix_sort_impl(&mut keys, |x| x.0);
+    let mut perm = keys.into_iter().map(|x| x.1).collect::<Vec<_>>();
+    apply_permutation(data, &mut perm);
+}
+//}}}
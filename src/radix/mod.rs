@@ -0,0 +1,78 @@
+//! Radix sorts on integer and float keys: LSD radix sort and MSD radix sort
+//! (American flag sort).
+//!
+//! Keys are sorted byte by byte on an unsigned encoding which orders like
+//! the key: signed integers get their sign bit flipped, floats are sorted by
+//! their IEEE 754 total order, in which `-0.0 < 0.0` and NaNs with the sign
+//! bit set (resp. unset) come before `-∞` (resp. after `+∞`).
+mod lsd;
+mod msd;
+pub use lsd::{lsd_radix_sort, lsd_radix_sort_by_key};
+pub use msd::{msd_radix_sort, msd_radix_sort_by_key};
+
+//{{{ Trait `RadixKey`
+/// A key which can be sorted byte by byte.
+pub trait RadixKey: Copy {
+    /// Width of the key in bytes.
+    const BYTES: usize;
+    /// Byte `ind` of the key, counting from the least significant one, in an
+    /// unsigned encoding which orders like the key.
+    fn byte(self, ind: usize) -> u8;
+}
+
+macro_rules! radix_key_unsigned {
+    ($($ty:ty),*) => {$(
+        impl RadixKey for $ty {
+            const BYTES: usize = std::mem::size_of::<$ty>();
+            #[inline]
+            fn byte(self, ind: usize) -> u8 { (self >> (8 * ind)) as u8 }
+        }
+    )*};
+}
+
+macro_rules! radix_key_signed {
+    ($($ty:ty => $uty:ty),*) => {$(
+        impl RadixKey for $ty {
+            const BYTES: usize = std::mem::size_of::<$ty>();
+            #[inline]
+            fn byte(self, ind: usize) -> u8 {
+                (self as $uty ^ (<$uty>::MAX / 2 + 1)).byte(ind)
+            }
+        }
+    )*};
+}
+
+macro_rules! radix_key_float {
+    ($($ty:ty => $uty:ty),*) => {$(
+        impl RadixKey for $ty {
+            const BYTES: usize = std::mem::size_of::<$ty>();
+            #[inline]
+            fn byte(self, ind: usize) -> u8 {
+                let bits = self.to_bits();
+                let sign = <$uty>::MAX / 2 + 1;
+                if bits & sign != 0 { !bits } else { bits | sign }.byte(ind)
+            }
+        }
+    )*};
+}
+
+radix_key_unsigned!(u8, u16, u32, u64, u128, usize);
+radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+radix_key_float!(f32 => u32, f64 => u64);
+//}}}
+//{{{ Permutation
+// Moves `data[perm[ind]]` to `data[ind]` for every `ind`, following the
+// cycles of `perm` with swaps. `perm` is left as the identity.
+fn apply_permutation<T>(data: &mut [T], perm: &mut [usize]) {
+    for start in 0 .. data.len() {
+        let mut ind = start;
+        while perm[ind] != start {
+            let next = perm[ind];
+            data.swap(ind, next);
+            perm[ind] = ind;
+            ind = next;
+        }
+        perm[ind] = ind;
+    }
+}
+//}}}
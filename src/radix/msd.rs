@@ -0,0 +1,73 @@
+//{{{ MSD radix sort
+use super::RadixKey;
+use crate::bisection::insertion_sort_impl;
+
+// Buckets up to this length are finished by insertion sort.
+const INSERTION_THRESHOLD: usize = 32;
+
+// Compares the `level` least significant bytes of `a` and `b`.
+fn key_less<K>(a: K, b: K, level: usize) -> bool
+where K: RadixKey {
+    for ind in (0 .. level).rev() {
+        let (x, y) = (a.byte(ind), b.byte(ind));
+        if x != y { return x < y; }
+    }
+    false
+}
+
+// Sorts `data` on the `level` least significant bytes of the keys, all other
+// bytes being equal.
+fn msd_radix_sort_impl<T, K, F>(data: &mut [T], level: usize, key: &mut F)
+where K: RadixKey, F: FnMut(&T) -> K {
+    let n = data.len();
+    if n <= INSERTION_THRESHOLD {
+        insertion_sort_impl(data, &mut |a, b| key_less(key(a), key(b), level));
+        return;
+    }
+    let ind = level - 1;
+    let mut count = [0usize; 256];
+    for x in data.iter() { count[key(x).byte(ind) as usize] += 1; }
+    let (mut start, mut end) = ([0usize; 256], [0usize; 256]);
+    let mut sum = 0;
+    for b in 0 .. 256 {
+        start[b] = sum;
+        sum += count[b];
+        end[b] = sum;
+    }
+    // Each element is swapped straight into the next free slot of its bucket.
+    let mut next = start;
+    for b in 0 .. 256 {
+        while next[b] < end[b] {
+            let d = key(&data[next[b]]).byte(ind) as usize;
+            if d != b { data.swap(next[b], next[d]); }
+            next[d] += 1;
+        }
+    }
+    if ind == 0 { return; }
+    for b in 0 .. 256 {
+        if count[b] > 1 {
+            msd_radix_sort_impl(&mut data[start[b] .. end[b]], ind, key);
+        }
+    }
+}
+
+/// Sorts `data` with MSD radix sort in place (American flag sort).
+///
+/// Elements are distributed into 256 buckets by the most significant byte
+/// of the key, permuting them in place with swaps, and each bucket is sorted
+/// recursively on the following bytes. Small buckets are finished by
+/// insertion sort. Runs in `O(n)` for keys of fixed width.
+pub fn msd_radix_sort<T>(data: &mut [T])
+where T: RadixKey {
+    msd_radix_sort_impl(data, T::BYTES, &mut |x: &T| *x);
+}
+
+/// Sorts `data` with MSD radix sort in place by the key extracted with `f`.
+///
+/// `f` is called again whenever a key is needed, so it should be cheap. The
+/// sort is not stable.
+pub fn msd_radix_sort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where K: RadixKey, F: FnMut(&T) -> K {
+    msd_radix_sort_impl(data, K::BYTES, &mut f);
+}
+//}}}
@@ -0,0 +1,68 @@
+//! Sorting networks of the least size known for up to [`SMALL_MAX`]
+//! elements, generated at compile time by `gen_sorting_network!`.
+//!
+//! The networks make a branchless sort of tiny slices, which quick sort and
+//! the recursive bitonic sorts use as their base case. They are not stable.
+//{{{ Sort of small slices
+use std::cmp::Ordering;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use macro_leon::gen_sorting_network;
+
+/// Largest length sorted by a fixed network.
+pub const SMALL_MAX: usize = 16;
+
+pub(crate) const NETWORKS: [&[&[(usize, usize)]]; SMALL_MAX + 1] = [
+    gen_sorting_network!(0),  gen_sorting_network!(1),  gen_sorting_network!(2),
+    gen_sorting_network!(3),  gen_sorting_network!(4),  gen_sorting_network!(5),
+    gen_sorting_network!(6),  gen_sorting_network!(7),  gen_sorting_network!(8),
+    gen_sorting_network!(9),  gen_sorting_network!(10), gen_sorting_network!(11),
+    gen_sorting_network!(12), gen_sorting_network!(13), gen_sorting_network!(14),
+    gen_sorting_network!(15), gen_sorting_network!(16),
+];
+
+// Puts the lesser of `*pi` and `*pj` at `pi`. Both are written back through
+// pointers selected by the comparison rather than by a branch on it; since
+// `is_less` runs before anything moves, a panic leaves both in place.
+unsafe fn swap_if_less<T, F>(pi: *mut T, pj: *mut T, is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let swap = is_less(&*pj, &*pi);
+    let (lo, hi) = if swap { (pj, pi) } else { (pi, pj) };
+    let tmp = ManuallyDrop::new(ptr::read(hi));
+    ptr::copy(lo, pi, 1);
+    ptr::copy_nonoverlapping(&*tmp, pj, 1);
+}
+
+/// Sorts `data` of length up to [`SMALL_MAX`] with the network of its length.
+pub(crate) fn sort_small_impl<T, F>(data: &mut [T], is_less: &mut F)
+where F: FnMut(&T, &T) -> bool {
+    let network = NETWORKS[data.len()];
+    let ptr = data.as_mut_ptr();
+    for &(i, j) in network.iter().copied().flatten() {
+        unsafe { swap_if_less(ptr.add(i), ptr.add(j), is_less); }
+    }
+}
+
+/// Sorts the array `data` with the smallest sorting network known for its
+/// length `N`, without branching on the comparisons. `N` up to
+/// [`SMALL_MAX`] is checked at compile time.
+pub fn sort_small<const N: usize, T>(data: &mut [T; N])
+where T: PartialOrd {
+    const { assert!(N <= SMALL_MAX) };
+    sort_small_impl(data, &mut T::lt);
+}
+
+/// Sorts the array `data` with a fixed network according to `compare`.
+pub fn sort_small_by<const N: usize, T, F>(data: &mut [T; N], mut compare: F)
+where F: FnMut(&T, &T) -> Ordering {
+    const { assert!(N <= SMALL_MAX) };
+    sort_small_impl(data, &mut |a, b| compare(a, b) == Ordering::Less);
+}
+
+/// Sorts the array `data` with a fixed network by the key extracted with `f`.
+pub fn sort_small_by_key<const N: usize, T, K, F>(data: &mut [T; N], mut f: F)
+where K: PartialOrd, F: FnMut(&T) -> K {
+    const { assert!(N <= SMALL_MAX) };
+    sort_small_impl(data, &mut |a, b| f(a) < f(b));
+}
+//}}}
@@ -0,0 +1,191 @@
+//! A common interface over all sort algorithms of the crate.
+use std::fmt;
+use crate::{bitonic, heap, bisection, radix};
+use crate::radix::RadixKey;
+
+//{{{ Complexity
+/// Worst case running time of a sort, in terms of the input length `n`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Complexity {
+    /// `O(n)`, for keys of fixed width
+    N,
+    /// `O(n log n)`
+    NLogN,
+    /// `O(n log² n)`
+    NLog2N,
+    /// `O(n²)`
+    N2,
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Complexity::N      => "O(n)",
+            Complexity::NLogN  => "O(n log n)",
+            Complexity::NLog2N => "O(n log² n)",
+            Complexity::N2     => "O(n²)",
+        })
+    }
+}
+//}}}
+//{{{ Trait `Sorter`
+/// A sort algorithm together with a description of its properties.
+pub trait Sorter<T> {
+    /// Short name of the algorithm.
+    fn name(&self) -> &'static str;
+    /// Whether equal elements keep their relative order.
+    fn stable(&self) -> bool;
+    /// Whether the sort works without a buffer of elements besides the input.
+    fn in_place(&self) -> bool;
+    /// Whether the sort runs on more than one thread.
+    fn parallel(&self) -> bool;
+    /// Worst case running time.
+    fn worst_case(&self) -> Complexity;
+    /// Sorts `data` in ascending order.
+    fn sort(&self, data: &mut [T]);
+}
+//}}}
+//{{{ Implementations
+macro_rules! serial_sorter {
+    ($(#[$attr:meta])* $ty:ident, $func:path, $name:literal,
+     $stable:literal, $in_place:literal, $worst_case:ident) => {
+        serial_sorter!($(#[$attr])* $ty, $func, $name,
+                       $stable, $in_place, $worst_case, PartialOrd);
+    };
+    ($(#[$attr:meta])* $ty:ident, $func:path, $name:literal,
+     $stable:literal, $in_place:literal, $worst_case:ident, $bound:path) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $ty;
+
+        impl<T> Sorter<T> for $ty
+        where T: $bound {
+            fn name(&self) -> &'static str { $name }
+            fn stable(&self) -> bool { $stable }
+            fn in_place(&self) -> bool { $in_place }
+            fn parallel(&self) -> bool { false }
+            fn worst_case(&self) -> Complexity { Complexity::$worst_case }
+            fn sort(&self, data: &mut [T]) { $func(data) }
+        }
+    };
+}
+
+macro_rules! parallel_sorter {
+    ($(#[$attr:meta])* $ty:ident, $func:path, $name:literal,
+     $stable:literal, $in_place:literal, $worst_case:ident $(, $bound:path)?) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $ty {
+            /// The sort runs on `2^t_depth` threads.
+            pub t_depth: u32,
+        }
+
+        impl<T> Sorter<T> for $ty
+        where T: PartialOrd + Send $(+ $bound)? {
+            fn name(&self) -> &'static str { $name }
+            fn stable(&self) -> bool { $stable }
+            fn in_place(&self) -> bool { $in_place }
+            fn parallel(&self) -> bool { true }
+            fn worst_case(&self) -> Complexity { Complexity::$worst_case }
+            fn sort(&self, data: &mut [T]) { $func(data, self.t_depth) }
+        }
+    };
+}
+
+serial_sorter!(
+    /// [`bisection::quick_sort`]
+    QuickSort, bisection::quick_sort, "quick", false, true, N2);
+parallel_sorter!(
+    /// [`bisection::quick_p_sort`]
+    QuickPSort, bisection::quick_p_sort, "quick_p", false, true, N2, Sync);
+serial_sorter!(
+    /// [`bisection::intro_sort`]
+    IntroSort, bisection::intro_sort, "intro", false, true, NLogN);
+serial_sorter!(
+    /// [`bisection::pdq_sort`]
+    PdqSort, bisection::pdq_sort, "pdq", false, true, NLogN);
+serial_sorter!(
+    /// [`bisection::merge_sort`]
+    MergeSort, bisection::merge_sort, "merge", true, false, NLogN);
+parallel_sorter!(
+    /// [`bisection::merge_p_sort`]
+    MergePSort, bisection::merge_p_sort, "merge_p", true, false, NLogN);
+serial_sorter!(
+    /// [`bisection::tim_sort`]
+    TimSort, bisection::tim_sort, "tim", true, false, NLogN);
+serial_sorter!(
+    /// [`heap::heap_sort`]
+    HeapSort, heap::heap_sort, "heap", false, true, NLogN);
+serial_sorter!(
+    /// [`heap::dary_heap_sort`] on a 4-ary heap
+    Dary4HeapSort, heap::dary_heap_sort::<4, _>, "dary4_heap", false, true, NLogN);
+serial_sorter!(
+    /// [`heap::dary_heap_sort`] on an 8-ary heap
+    Dary8HeapSort, heap::dary_heap_sort::<8, _>, "dary8_heap", false, true, NLogN);
+serial_sorter!(
+    /// [`heap::weak_heap_sort`]
+    WeakHeapSort, heap::weak_heap_sort, "weak_heap", false, true, NLogN);
+serial_sorter!(
+    /// [`heap::smooth_b_sort`]
+    SmoothBSort, heap::smooth_b_sort, "smooth_b", false, true, NLogN);
+serial_sorter!(
+    /// [`heap::smooth_l_sort`]
+    SmoothLSort, heap::smooth_l_sort, "smooth_l", false, true, NLogN);
+serial_sorter!(
+    /// [`bitonic::bitonic_r_sort`]
+    BitonicRSort, bitonic::bitonic_r_sort, "bitonic_r", false, true, NLog2N);
+parallel_sorter!(
+    /// [`bitonic::bitonic_rp_sort`]
+    BitonicRpSort, bitonic::bitonic_rp_sort, "bitonic_rp", false, true, NLog2N);
+serial_sorter!(
+    /// [`bitonic::bitonic_i_sort`]
+    BitonicISort, bitonic::bitonic_i_sort, "bitonic_i", false, true, NLog2N);
+parallel_sorter!(
+    /// [`bitonic::bitonic_ip_sort`]
+    BitonicIpSort, bitonic::bitonic_ip_sort, "bitonic_ip", false, true, NLog2N);
+parallel_sorter!(
+    /// [`bitonic::sample_p_sort`]
+    SamplePSort, bitonic::sample_p_sort, "sample_p", false, false, NLogN, Sync);
+serial_sorter!(
+    /// [`radix::lsd_radix_sort`]
+    LsdRadixSort, radix::lsd_radix_sort, "lsd_radix", true, false, N, RadixKey);
+serial_sorter!(
+    /// [`radix::msd_radix_sort`]
+    MsdRadixSort, radix::msd_radix_sort, "msd_radix", false, true, N, RadixKey);
+//}}}
+//{{{ Registry
+/// Returns every sort algorithm of the crate, the parallel ones running on
+/// `2^t_depth` threads.
+pub fn registry<T>(t_depth: u32) -> Vec<Box<dyn Sorter<T>>>
+where T: PartialOrd + Send + Sync {
+    vec![
+        Box::new(QuickSort),
+        Box::new(QuickPSort { t_depth }),
+        Box::new(IntroSort),
+        Box::new(PdqSort),
+        Box::new(BitonicRSort),
+        Box::new(BitonicRpSort { t_depth }),
+        Box::new(BitonicISort),
+        Box::new(BitonicIpSort { t_depth }),
+        Box::new(SamplePSort { t_depth }),
+        Box::new(SmoothBSort),
+        Box::new(SmoothLSort),
+        Box::new(WeakHeapSort),
+        Box::new(HeapSort),
+        Box::new(Dary4HeapSort),
+        Box::new(Dary8HeapSort),
+        Box::new(MergeSort),
+        Box::new(MergePSort { t_depth }),
+        Box::new(TimSort),
+    ]
+}
+
+/// Returns every radix sort of the crate.
+pub fn radix_registry<T>() -> Vec<Box<dyn Sorter<T>>>
+where T: RadixKey {
+    vec![
+        Box::new(LsdRadixSort),
+        Box::new(MsdRadixSort),
+    ]
+}
+//}}}
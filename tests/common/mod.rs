@@ -0,0 +1,49 @@
+// Fixtures shared by the tests of the priority queues.
+#![allow(dead_code)]
+use std::cell::Cell;
+use std::cmp::Ordering;
+use rand::Rng;
+
+// The operations every max priority queue of the crate has.
+pub trait MaxHeap: Default {
+    fn push(&mut self, x: i32);
+    fn pop(&mut self) -> Option<i32>;
+    fn peek(&self) -> Option<&i32>;
+    fn len(&self) -> usize;
+}
+
+// Pushes `input` into an empty heap, popping at random in between, then
+// drains the heap, checking every step against a sorted reference.
+pub fn check_interleaved<H: MaxHeap>(input: &[i32], rng: &mut impl Rng) {
+    let mut heap = H::default();
+    let mut reference = vec![];
+    for &x in input {
+        heap.push(x);
+        reference.push(x);
+        if rng.gen_bool(0.3) {
+            reference.sort();
+            assert_eq!(heap.pop(), reference.pop());
+        }
+        reference.sort();
+        assert_eq!(heap.peek(), reference.last());
+        assert_eq!(heap.len(), reference.len());
+    }
+    while let Some(x) = heap.pop() {
+        assert_eq!(Some(x), reference.pop());
+    }
+    assert!(reference.is_empty());
+}
+
+// A value counting the comparisons made on it in a shared cell.
+#[derive(Clone, Copy, Debug)]
+pub struct Counted<'a, T>(pub T, pub &'a Cell<usize>);
+
+impl<T: PartialEq> PartialEq for Counted<'_, T> {
+    fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+}
+impl<T: PartialOrd> PartialOrd for Counted<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.1.set(self.1.get() + 1);
+        self.0.partial_cmp(&other.0)
+    }
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::heap::{self, DaryHeap};
+use common::{MaxHeap, check_interleaved};
+
+impl<const D: usize> MaxHeap for DaryHeap<i32, D> {
+    fn push(&mut self, x: i32) { DaryHeap::push(self, x); }
+    fn pop(&mut self) -> Option<i32> { DaryHeap::pop(self) }
+    fn peek(&self) -> Option<&i32> { DaryHeap::peek(self) }
+    fn len(&self) -> usize { DaryHeap::len(self) }
+}
+
+// Lengths around the ends of the first levels of a `D`-ary heap: the root
+// alone, one level below it partly or fully used, then two.
+fn check<const D: usize>(rng: &mut StdRng) {
+    let full = [1, 1 + D, 1 + D + D * D];
+    let lens = full.into_iter().flat_map(|n| [n - 1, n, n + 1]).chain([100, 1000]);
+    for n in lens {
+        let input = (0 .. n).map(|_| rng.gen_range(0 .. 50)).collect::<Vec<i32>>();
+        let mut expect = input.clone();
+        expect.sort();
+        let heap = DaryHeap::<_, D>::from_vec(input.clone());
+        assert_eq!(heap.into_sorted_vec(), expect, "D: {}, n: {}", D, n);
+        let mut data = input.clone();
+        heap::dary_heap_sort::<D, _>(&mut data);
+        assert_eq!(data, expect, "D: {}, n: {}", D, n);
+        check_interleaved::<DaryHeap<i32, D>>(&input, rng);
+    }
+}
+
+#[test]
+fn test_dary_heap() {
+    let mut rng = StdRng::seed_from_u64(0);
+    // The binary heap, an odd arity, the cache line sized ones, and a wide
+    // one, which holds a few hundred elements in three levels.
+    check::<2>(&mut rng);
+    check::<3>(&mut rng);
+    check::<4>(&mut rng);
+    check::<8>(&mut rng);
+    check::<16>(&mut rng);
+}
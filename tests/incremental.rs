@@ -0,0 +1,42 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::heap::{IncrementalSorter, SortedLazyExt};
+
+#[test]
+fn test_incremental_sorter() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut sorter = IncrementalSorter::new();
+    assert_eq!(sorter.drain_sorted().count(), 0);
+    // Drains at arbitrary points of the stream sort what came since the
+    // last one.
+    for n in [1, 2, 7, 100, 1000] {
+        let input = (0 .. n).map(|_| rng.gen_range(0 .. 100)).collect::<Vec<i32>>();
+        let (head, tail) = input.split_at(n / 2);
+        for &x in head { sorter.push(x); }
+        sorter.extend(tail.iter().copied());
+        assert_eq!(sorter.len(), n);
+        assert_eq!(sorter.peek_max(), input.iter().max());
+        let mut expect = input.clone();
+        expect.sort();
+        assert_eq!(sorter.drain_sorted().collect::<Vec<_>>(), expect, "n: {}", n);
+        assert!(sorter.is_empty());
+
+        let mut sorter = input.iter().copied().collect::<IncrementalSorter<_>>();
+        expect.reverse();
+        assert_eq!(sorter.drain_lazy().collect::<Vec<_>>(), expect, "n: {}", n);
+        assert!(sorter.is_empty());
+    }
+}
+
+#[test]
+fn test_sorted_lazy() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let input = (0 .. 1000).map(|_| rng.gen::<i64>()).collect::<Vec<_>>();
+    let mut expect = input.clone();
+    expect.sort_by(|a, b| b.cmp(a));
+    let mut lazy = input.iter().sorted_lazy();
+    assert_eq!(lazy.len(), 1000);
+    assert_eq!(lazy.by_ref().take(10).copied().collect::<Vec<_>>(), expect[.. 10]);
+    assert_eq!(lazy.len(), 990);
+    assert_eq!(lazy.copied().collect::<Vec<_>>(), expect[10 ..]);
+    assert_eq!(Vec::<u8>::new().into_iter().sorted_lazy().next(), None);
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::cell::Cell;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::heap::LeonardoHeap;
+use common::{MaxHeap, Counted, check_interleaved};
+
+impl MaxHeap for LeonardoHeap<i32> {
+    fn push(&mut self, x: i32) { LeonardoHeap::push(self, x); }
+    fn pop(&mut self) -> Option<i32> { self.pop_max() }
+    fn peek(&self) -> Option<&i32> { LeonardoHeap::peek(self) }
+    fn len(&self) -> usize { LeonardoHeap::len(self) }
+}
+
+#[test]
+fn test_leonardo_heap() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for n in [0, 1, 2, 3, 4, 5, 8, 9, 15, 100, 1000] {
+        let input = (0 .. n).map(|_| rng.gen_range(0 .. 50)).collect::<Vec<i32>>();
+        check_interleaved::<LeonardoHeap<i32>>(&input, &mut rng);
+    }
+}
+
+#[test]
+fn test_leonardo_heap_shapes() {
+    // Every forest of Leonardo trees up to 200 elements, built from the
+    // elements in ascending order, in descending order, which moves every
+    // root, and at random. Popping then takes the last tree apart, one shape
+    // after the other.
+    let mut rng = StdRng::seed_from_u64(0);
+    for n in 0 .. 200 {
+        let ascending = (0 .. n).collect::<Vec<i32>>();
+        let descending = ascending.iter().rev().copied().collect();
+        let random = (0 .. n).map(|_| rng.gen_range(0 .. n)).collect();
+        for input in [ascending, descending, random] {
+            let mut expect = input.clone();
+            expect.sort();
+            let mut heap = LeonardoHeap::from_vec(input.clone());
+            for x in expect.iter().rev() {
+                assert_eq!(heap.peek(), Some(x), "n: {}", n);
+                assert_eq!(heap.pop_max(), Some(*x), "n: {}", n);
+            }
+            assert!(heap.is_empty());
+            assert_eq!(LeonardoHeap::from_vec(input).into_sorted_vec(), expect, "n: {}", n);
+        }
+    }
+}
+
+#[test]
+fn test_leonardo_heap_adaptive() {
+    // Sorting sorted input, or sorted input with a few swaps, takes a linear
+    // number of comparisons.
+    let n = 1 << 14;
+    let mut rng = StdRng::seed_from_u64(0);
+    for swaps in [0, 10] {
+        let count = Cell::new(0);
+        let mut input = (0 .. n).map(|x| Counted(x, &count)).collect::<Vec<_>>();
+        for _ in 0 .. swaps {
+            input.swap(rng.gen_range(0 .. n as usize), rng.gen_range(0 .. n as usize));
+        }
+        let sorted = LeonardoHeap::from_vec(input).into_sorted_vec();
+        assert!(sorted.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert!(count.get() < 8 * n as usize, "swaps: {}, comparisons: {}", swaps, count.get());
+
+        let count = Cell::new(0);
+        let mut heap = LeonardoHeap::new();
+        for x in 0 .. n { heap.push(Counted(x, &count)); }
+        while heap.pop_max().is_some() {}
+        assert!(count.get() < 8 * n as usize, "comparisons: {}", count.get());
+    }
+}
@@ -0,0 +1,78 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::bitonic::{self, SortingNetwork};
+
+#[test]
+fn test_bitonic_network() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for n in 0 .. 70 {
+        let network = SortingNetwork::bitonic(n);
+        assert_eq!(network.len(), n);
+        let input = (0 .. n).map(|_| rng.gen_range(0 .. 10)).collect::<Vec<i32>>();
+        let mut expect = input.clone();
+        bitonic::bitonic_i_sort(&mut expect);
+        let mut data = input.clone();
+        network.apply(&mut data);
+        assert_eq!(data, expect, "n: {}", n);
+        let mut data = input.clone();
+        network.apply_by(&mut data, |a, b| b.cmp(a));
+        assert!(data.windows(2).all(|w| w[0] >= w[1]), "n: {}", n);
+    }
+}
+
+#[test]
+fn test_bitonic_network_size() {
+    // The power-of-two network has `k (k + 1) / 2` layers of `n / 2`
+    // comparators each for `n = 2^k`.
+    for k in 1 .. 8 {
+        let network = SortingNetwork::bitonic(1 << k);
+        assert_eq!(network.depth(), k * (k + 1) / 2);
+        assert_eq!(network.size(), network.depth() << (k - 1));
+        assert!(network.layers().iter().all(|layer| layer.len() == 1 << (k - 1)));
+    }
+    assert_eq!(SortingNetwork::bitonic(1).size(), 0);
+    assert!(SortingNetwork::bitonic(0).is_empty());
+}
+
+#[test]
+fn test_network_export() {
+    let network = SortingNetwork::new(3, vec![vec![(0, 1)], vec![(1, 2)], vec![(1, 0)]]);
+    assert_eq!(network.to_json(), r#"{"n": 3, "layers": [[[0, 1]], [[1, 2]], [[1, 0]]]}"#);
+    let dot = network.to_dot();
+    assert!(dot.starts_with("digraph network {"));
+    assert!(dot.contains("w0_0 -> w0_1 -> w0_2 -> w0_3 [arrowhead=none];"));
+    assert!(dot.contains("w1_3 -> w0_3 [constraint=false];"));
+    let svg = network.to_svg();
+    assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<circle").count(), network.size());
+    assert_eq!(svg.matches("<line").count(), network.size() + network.len());
+}
+
+#[test]
+#[should_panic]
+fn test_network_shared_wire() {
+    SortingNetwork::new(3, vec![vec![(0, 1), (1, 2)]]);
+}
+
+#[test]
+#[should_panic]
+fn test_network_wire_out_of_range() {
+    SortingNetwork::new(2, vec![vec![(0, 2)]]);
+}
+
+#[test]
+fn test_optimal_network_size() {
+    // Best known sizes and depths on 0 to 16 wires.
+    const SIZE: [usize; 17] = [0, 0, 1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60];
+    for (n, size) in SIZE.into_iter().enumerate() {
+        let network = SortingNetwork::optimal(n);
+        assert_eq!(network.len(), n);
+        assert_eq!(network.size(), size, "n: {}", n);
+        assert!(network.depth() <= 10, "n: {}", n);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_optimal_network_too_large() {
+    SortingNetwork::optimal(17);
+}
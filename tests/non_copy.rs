@@ -0,0 +1,203 @@
+use std::cmp::Ordering;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::{bitonic, heap, bisection};
+use sort_algo::sorter::registry;
+
+// A heap allocated element which records how many times it has been dropped.
+#[derive(Debug)]
+struct Tracked {
+    key   : Box<i32>,
+    id    : usize,
+    drops : Arc<Vec<AtomicUsize>>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops[self.id].fetch_add(1, Relaxed);
+    }
+}
+impl PartialEq for Tracked {
+    fn eq(&self, other: &Self) -> bool { self.key == other.key }
+}
+impl PartialOrd for Tracked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { self.key.partial_cmp(&other.key) }
+}
+
+fn tracked(len: usize, seed: u64) -> (Vec<Tracked>, Arc<Vec<AtomicUsize>>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let drops = Arc::new((0 .. len).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>());
+    let data = (0 .. len).map(|id| Tracked {
+        key: Box::new(rng.gen_range(0 .. len as i32 / 2 + 1)), id, drops: drops.clone(),
+    }).collect();
+    (data, drops)
+}
+
+fn assert_permutation(data: &[Tracked], len: usize) {
+    let mut ids = data.iter().map(|x| x.id).collect::<Vec<_>>();
+    ids.sort();
+    assert_eq!(ids, (0 .. len).collect::<Vec<_>>());
+}
+
+fn assert_dropped_once(drops: &[AtomicUsize]) {
+    for (id, d) in drops.iter().enumerate() {
+        assert_eq!(d.load(Relaxed), 1, "element {} dropped {} times", id, d.load(Relaxed));
+    }
+}
+
+#[test]
+fn test_strings() {
+    let mut rng = StdRng::seed_from_u64(3);
+    for len in [0, 1, 2, 10, 33, 300] {
+        let input = (0 .. len).map(|_| format!("{:x}", rng.gen_range(0 .. 1000)))
+            .collect::<Vec<String>>();
+        let mut expect = input.clone();
+        expect.sort();
+        for sorter in registry(2) {
+            let mut data = input.clone();
+            sorter.sort(&mut data);
+            assert_eq!(data, expect, "{}", sorter.name());
+        }
+    }
+}
+
+#[test]
+fn test_no_leak_no_double_drop() {
+    for len in [0, 1, 2, 17, 100, 257] {
+        for sorter in registry(2) {
+            let (mut data, drops) = tracked(len, len as u64);
+            sorter.sort(&mut data);
+            assert!(data.windows(2).all(|w| w[0].key <= w[1].key), "{}", sorter.name());
+            assert_permutation(&data, len);
+            drop(data);
+            assert_dropped_once(&drops);
+        }
+    }
+}
+
+type SortBy = fn(&mut [Tracked], &mut dyn FnMut(&Tracked, &Tracked) -> Ordering);
+
+#[test]
+fn test_panic_safety() {
+    let sorts: &[(&str, SortBy)] = &[
+        ("quick", |d, c| bisection::quick_sort_by(d, c)),
+        ("intro", |d, c| bisection::intro_sort_by(d, c)),
+        ("pdq", |d, c| bisection::pdq_sort_by(d, c)),
+        ("merge", |d, c| bisection::merge_sort_by(d, c)),
+        ("tim", |d, c| bisection::tim_sort_by(d, c)),
+        ("heap", |d, c| heap::heap_sort_by(d, c)),
+        ("dary_heap", |d, c| heap::dary_heap_sort_by::<4, _, _>(d, c)),
+        ("weak_heap", |d, c| heap::weak_heap_sort_by(d, c)),
+        ("smooth_b", |d, c| heap::smooth_b_sort_by(d, c)),
+        ("smooth_l", |d, c| heap::smooth_l_sort_by(d, c)),
+        ("bitonic_r", |d, c| bitonic::bitonic_r_sort_by(d, c)),
+        ("bitonic_i", |d, c| bitonic::bitonic_i_sort_by(d, c)),
+    ];
+    let len = 200;
+    for &(name, sort) in sorts {
+        for limit in [0, 1, 10, 100, 500, 1000] {
+            let (mut data, drops) = tracked(len, limit as u64);
+            let mut count = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| sort(&mut data, &mut |a, b| {
+                count += 1;
+                if count > limit { panic!("comparison limit reached"); }
+                a.key.cmp(&b.key)
+            })));
+            assert!(result.is_err(), "{} finished within {} comparisons", name, limit);
+            assert_permutation(&data, len);
+            drop(data);
+            assert_dropped_once(&drops);
+        }
+    }
+}
+
+#[test]
+fn test_parallel_panic_safety() {
+    type ParSortBy = fn(&mut [Tracked], u32, &(dyn Fn(&Tracked, &Tracked) -> Ordering + Sync));
+    let sorts: &[(&str, ParSortBy)] = &[
+        ("quick_p", |d, t, c| bisection::quick_p_sort_by(d, t, c)),
+        ("merge_p", |d, t, c| bisection::merge_p_sort_by(d, t, c)),
+        ("bitonic_rp", |d, t, c| bitonic::bitonic_rp_sort_by(d, t, c)),
+        ("bitonic_ip", |d, t, c| bitonic::bitonic_ip_sort_by(d, t, c)),
+        ("sample_p", |d, t, c| bitonic::sample_p_sort_by(d, t, c)),
+    ];
+    let len = 200;
+    for &(name, sort) in sorts {
+        for t_depth in [1, 2, 3] {
+            for limit in [0, 1, 100, 1000] {
+                let (mut data, drops) = tracked(len, limit as u64);
+                let count = AtomicUsize::new(0);
+                let result = catch_unwind(AssertUnwindSafe(|| sort(&mut data, t_depth, &|a, b| {
+                    if count.fetch_add(1, Relaxed) >= limit { panic!("comparison limit reached"); }
+                    a.key.cmp(&b.key)
+                })));
+                assert!(result.is_err(), "{} finished within {} comparisons", name, limit);
+                assert_permutation(&data, len);
+                drop(data);
+                assert_dropped_once(&drops);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parallel_borrowed() {
+    let text = (0 .. 300).map(|x| format!("{:03}", x * 7 % 300)).collect::<Vec<String>>();
+    let mut expect = text.iter().map(String::as_str).collect::<Vec<&str>>();
+    expect.sort();
+    for t_depth in [0, 1, 2, 3] {
+        let mut data = text.iter().map(String::as_str).collect::<Vec<&str>>();
+        bitonic::bitonic_rp_sort(&mut data, t_depth);
+        assert_eq!(data, expect);
+        let mut data = text.iter().map(String::as_str).collect::<Vec<&str>>();
+        bitonic::bitonic_ip_sort(&mut data, t_depth);
+        assert_eq!(data, expect);
+    }
+}
+
+#[test]
+fn test_merge_p_sort_panic_safety() {
+    let len = 20000;
+    for limit in [1000, 100000, 250000] {
+        let (mut data, drops) = tracked(len, limit as u64);
+        let count = AtomicUsize::new(0);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            bisection::merge_p_sort_by(&mut data, 3, |a: &Tracked, b: &Tracked| {
+                if count.fetch_add(1, Relaxed) >= limit { panic!("comparison limit reached"); }
+                a.key.cmp(&b.key)
+            })
+        }));
+        assert!(result.is_err(), "finished within {} comparisons", limit);
+        assert_permutation(&data, len);
+        drop(data);
+        assert_dropped_once(&drops);
+    }
+}
+
+#[test]
+fn test_parallel_panic_safety_large() {
+    type ParSortBy = fn(&mut [Tracked], u32, &(dyn Fn(&Tracked, &Tracked) -> Ordering + Sync));
+    let sorts: &[(&str, ParSortBy)] = &[
+        ("quick_p", |d, t, c| bisection::quick_p_sort_by(d, t, c)),
+        ("sample_p", |d, t, c| bitonic::sample_p_sort_by(d, t, c)),
+    ];
+    let len = 50000;
+    // In the partitions or the sample, in the classification and at the
+    // bottom of the recursion or in the buckets.
+    for &(name, sort) in sorts {
+        for limit in [1000, 100000, 500000] {
+            let (mut data, drops) = tracked(len, limit as u64);
+            let count = AtomicUsize::new(0);
+            let result = catch_unwind(AssertUnwindSafe(|| sort(&mut data, 2, &|a, b| {
+                if count.fetch_add(1, Relaxed) >= limit { panic!("comparison limit reached"); }
+                a.key.cmp(&b.key)
+            })));
+            assert!(result.is_err(), "{} finished within {} comparisons", name, limit);
+            assert_permutation(&data, len);
+            drop(data);
+            assert_dropped_once(&drops);
+        }
+    }
+}
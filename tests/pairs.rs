@@ -0,0 +1,65 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::bitonic;
+
+fn keys(len: usize, seed: u64) -> Vec<f32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0 .. len).map(|_| rng.gen_range(0 .. 16) as f32 / 4.0).collect()
+}
+
+fn check_pairs(sort_pairs: impl Fn(&mut [f32], &mut [usize])) {
+    for len in [0, 1, 2, 3, 7, 8, 100, 1000, 1025] {
+        let input = keys(len, len as u64);
+        let mut expect = input.clone();
+        expect.sort_by(f32::total_cmp);
+        let mut data = input.clone();
+        let mut values = (0 .. len).collect::<Vec<usize>>();
+        sort_pairs(&mut data, &mut values);
+        assert_eq!(data, expect, "len: {}", len);
+        // Every value still travels with its key, and the serial network
+        // makes the same exchanges.
+        assert!(values.iter().zip(&data).all(|(&v, &k)| input[v] == k), "len: {}", len);
+        assert_eq!(values, bitonic::bitonic_i_argsort(&input), "len: {}", len);
+    }
+}
+
+#[test]
+fn test_bitonic_i_sort_pairs() {
+    check_pairs(bitonic::bitonic_i_sort_pairs);
+    check_pairs(|k, v| bitonic::bitonic_i_sort_pairs_by(k, v, f32::total_cmp));
+    check_pairs(|k, v| bitonic::bitonic_i_sort_pairs_by_key(k, v, |&k| k));
+}
+
+#[test]
+fn test_bitonic_ip_sort_pairs() {
+    for t_depth in 0 ..= 3 {
+        check_pairs(|k, v| bitonic::bitonic_ip_sort_pairs(k, v, t_depth));
+        check_pairs(|k, v| bitonic::bitonic_ip_sort_pairs_by(k, v, t_depth, f32::total_cmp));
+        check_pairs(|k, v| bitonic::bitonic_ip_sort_pairs_by_key(k, v, t_depth, |&k| k));
+    }
+}
+
+#[test]
+fn test_bitonic_argsort() {
+    for len in [0, 1, 5, 64, 1000] {
+        let input = keys(len, 7);
+        let perm = bitonic::bitonic_i_argsort(&input);
+        let mut seen = vec![false; len];
+        perm.iter().for_each(|&i| seen[i] = true);
+        assert!(seen.into_iter().all(|x| x));
+        assert!(perm.windows(2).all(|w| input[w[0]] <= input[w[1]]));
+        assert_eq!(bitonic::bitonic_i_argsort_by(&input, f32::total_cmp), perm);
+        assert_eq!(bitonic::bitonic_i_argsort_by_key(&input, |&k| -k).len(), len);
+        for t_depth in 0 ..= 3 {
+            assert_eq!(bitonic::bitonic_ip_argsort(&input, t_depth), perm);
+            assert_eq!(bitonic::bitonic_ip_argsort_by(&input, t_depth, f32::total_cmp), perm);
+            let rev = bitonic::bitonic_ip_argsort_by_key(&input, t_depth, |&k| -k);
+            assert!(rev.windows(2).all(|w| input[w[0]] >= input[w[1]]));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_bitonic_sort_pairs_lengths() {
+    bitonic::bitonic_i_sort_pairs(&mut [1, 2, 3], &mut [0, 1]);
+}
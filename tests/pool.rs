@@ -0,0 +1,96 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::bitonic;
+use sort_algo::pool::Pool;
+
+#[test]
+fn test_run() {
+    let pool = Pool::new(5);
+    assert_eq!(pool.threads(), 5);
+    let hits = (0 .. 5).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+    for _ in 0 .. 100 {
+        pool.run(&|ind| { hits[ind].fetch_add(1, Relaxed); });
+    }
+    assert!(hits.iter().all(|h| h.load(Relaxed) == 100));
+}
+
+#[test]
+fn test_run_panic() {
+    let pool = Pool::new(4);
+    let result = catch_unwind(AssertUnwindSafe(|| pool.run(&|ind| {
+        if ind == 2 { panic!("worker {}", ind); }
+    })));
+    assert!(result.is_err());
+    let count = AtomicUsize::new(0);
+    pool.run(&|_| { count.fetch_add(1, Relaxed); });
+    assert_eq!(count.load(Relaxed), 4);
+}
+
+#[test]
+fn test_bitonic_ip_sort_with() {
+    let mut rng = StdRng::seed_from_u64(4);
+    for threads in [1, 3, 4, 8] {
+        let pool = Pool::new(threads);
+        for len in [0, 1, 2, 7, 64, 100, 1000, 4097] {
+            let input = (0 .. len).map(|_| rng.gen_range(-100 .. 100)).collect::<Vec<i32>>();
+            let mut expect = input.clone();
+            expect.sort();
+            let mut data = input.clone();
+            bitonic::bitonic_ip_sort_with(&mut data, &pool);
+            assert_eq!(data, expect);
+            // Borrowed, non-'static comparators are fine on a caller-owned pool.
+            let offset = 3;
+            let mut data = input.clone();
+            bitonic::bitonic_ip_sort_by_with(&mut data, &pool, |a, b| (b + offset).cmp(&(a + offset)));
+            assert!(data.iter().eq(expect.iter().rev()));
+            let mut data = input.clone();
+            bitonic::bitonic_ip_sort_by_key_with(&mut data, &pool, |x| x.wrapping_neg());
+            assert!(data.iter().eq(expect.iter().rev()));
+        }
+    }
+}
+
+#[test]
+fn test_bitonic_ip_sort_panic() {
+    let pool = Pool::new(4);
+    let mut rng = StdRng::seed_from_u64(5);
+    let input = (0 .. 1000).map(|_| rng.gen::<i32>()).collect::<Vec<_>>();
+    for limit in [0, 10, 1000, 5000] {
+        let mut data = input.clone();
+        let count = AtomicUsize::new(0);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            bitonic::bitonic_ip_sort_by_with(&mut data, &pool, |a, b| {
+                if count.fetch_add(1, Relaxed) >= limit { panic!("comparison limit reached"); }
+                a.cmp(b)
+            });
+        }));
+        assert!(result.is_err());
+        data.sort();
+        let mut expect = input.clone();
+        expect.sort();
+        assert_eq!(data, expect);
+    }
+    let mut data = input.clone();
+    bitonic::bitonic_ip_sort_with(&mut data, &pool);
+    assert!(data.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_rayon_pool() {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+    let mut rng = StdRng::seed_from_u64(6);
+    let input = (0 .. 5000).map(|_| rng.gen::<i32>()).collect::<Vec<_>>();
+    let mut expect = input.clone();
+    expect.sort();
+    let sorts: &[fn(&mut [i32], u32)] = &[
+        bitonic::bitonic_rp_sort, bitonic::bitonic_ip_sort,
+        sort_algo::bisection::merge_p_sort, sort_algo::bisection::quick_p_sort,
+    ];
+    for sort in sorts {
+        let mut data = input.clone();
+        pool.install(|| sort(&mut data, 3));
+        assert_eq!(data, expect);
+    }
+}
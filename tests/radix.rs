@@ -0,0 +1,114 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rand::distributions::{Distribution, Standard};
+use sort_algo::radix;
+
+const LENS: &[usize] = &[0, 1, 2, 3, 31, 32, 33, 100, 257, 1000, 5000];
+
+fn check_ints<T>(seed: u64)
+where T: radix::RadixKey + Ord + std::fmt::Debug, Standard: Distribution<T> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    for &len in LENS {
+        let input = (0 .. len).map(|_| rng.gen()).collect::<Vec<T>>();
+        let mut expect = input.clone();
+        expect.sort();
+        for sort in [radix::lsd_radix_sort::<T>, radix::msd_radix_sort::<T>] {
+            let mut data = input.clone();
+            sort(&mut data);
+            assert_eq!(data, expect);
+        }
+    }
+}
+
+#[test]
+fn test_unsigned() {
+    check_ints::<u8>(1);
+    check_ints::<u16>(2);
+    check_ints::<u32>(3);
+    check_ints::<u64>(4);
+    check_ints::<u128>(5);
+    check_ints::<usize>(6);
+}
+
+#[test]
+fn test_signed() {
+    check_ints::<i8>(7);
+    check_ints::<i16>(8);
+    check_ints::<i32>(9);
+    check_ints::<i64>(10);
+    check_ints::<i128>(11);
+    check_ints::<isize>(12);
+}
+
+#[test]
+fn test_patterns() {
+    let n = 10000;
+    let patterns: Vec<Vec<i64>> = vec![
+        (0 .. n).collect(),
+        (0 .. n).rev().collect(),
+        vec![-7; n as usize],
+        (0 .. n).map(|x| x % 3 - 1).collect(),
+        (0 .. n).map(|x| if x % 2 == 0 { i64::MIN + x } else { i64::MAX - x }).collect(),
+        (0 .. n).map(|x| x << 40).collect(),
+    ];
+    for input in patterns {
+        let mut expect = input.clone();
+        expect.sort();
+        for sort in [radix::lsd_radix_sort::<i64>, radix::msd_radix_sort::<i64>] {
+            let mut data = input.clone();
+            sort(&mut data);
+            assert_eq!(data, expect);
+        }
+    }
+}
+
+#[test]
+fn test_floats() {
+    let mut rng = StdRng::seed_from_u64(13);
+    let special = [0.0, -0.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, -f64::NAN,
+                   f64::MIN_POSITIVE, -f64::MIN_POSITIVE, f64::MAX, f64::MIN];
+    for &len in LENS {
+        let input = (0 .. len).map(|ind| if ind % 10 == 0 {
+            special[rng.gen_range(0 .. special.len())]
+        } else {
+            rng.gen_range(-1e3 .. 1e3)
+        }).collect::<Vec<f64>>();
+        let mut expect = input.clone();
+        expect.sort_by(f64::total_cmp);
+        for sort in [radix::lsd_radix_sort::<f64>, radix::msd_radix_sort::<f64>] {
+            let mut data = input.clone();
+            sort(&mut data);
+            assert_eq!(data.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+                       expect.iter().map(|x| x.to_bits()).collect::<Vec<_>>());
+        }
+        let input = input.iter().map(|&x| x as f32).collect::<Vec<f32>>();
+        let mut expect = input.clone();
+        expect.sort_by(f32::total_cmp);
+        for sort in [radix::lsd_radix_sort::<f32>, radix::msd_radix_sort::<f32>] {
+            let mut data = input.clone();
+            sort(&mut data);
+            assert_eq!(data.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+                       expect.iter().map(|x| x.to_bits()).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn test_by_key() {
+    let mut rng = StdRng::seed_from_u64(14);
+    for &len in LENS {
+        let input = (0 .. len).map(|ind| (rng.gen_range(-20i16 .. 20), ind.to_string()))
+            .collect::<Vec<(i16, String)>>();
+        let mut expect = input.clone();
+        expect.sort_by_key(|x| x.0);
+        let mut data = input.clone();
+        radix::lsd_radix_sort_by_key(&mut data, |x| x.0);
+        assert_eq!(data, expect);
+        let mut data = input.clone();
+        radix::msd_radix_sort_by_key(&mut data, |x| x.0);
+        assert!(data.windows(2).all(|w| w[0].0 <= w[1].0));
+        data.sort();
+        let mut input = input;
+        input.sort();
+        assert_eq!(data, input);
+    }
+}
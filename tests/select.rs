@@ -0,0 +1,162 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::{bisection, heap};
+
+fn inputs() -> Vec<Vec<i32>> {
+    let mut rng = StdRng::seed_from_u64(0x5e1);
+    let mut ret = vec![];
+    for len in [1usize, 2, 3, 5, 16, 17, 100, 257, 1000] {
+        ret.push((0 .. len).map(|_| rng.gen()).collect());
+        ret.push((0 .. len).map(|_| rng.gen_range(0 .. 4)).collect());
+        ret.push((0 .. len as i32).collect());
+        ret.push((0 .. len as i32).rev().collect());
+    }
+    ret
+}
+
+#[test]
+fn test_select_nth() {
+    for input in inputs() {
+        let mut expect = input.clone();
+        expect.sort();
+        let n = input.len();
+        for k in [0, 1, n / 3, n / 2, n.saturating_sub(2), n - 1].into_iter().filter(|&k| k < n) {
+            let mut data = input.clone();
+            bisection::select_nth(&mut data, k);
+            assert_eq!(data[k], expect[k], "input: {:?}, k: {}", input, k);
+            assert!(data[.. k].iter().all(|x| *x <= data[k]));
+            assert!(data[k + 1 ..].iter().all(|x| *x >= data[k]));
+            data.sort();
+            assert_eq!(data, expect);
+        }
+    }
+}
+
+#[test]
+fn test_select_nth_by() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let input = (0 .. 500).map(|_| (rng.gen_range(0u8 .. 10), rng.gen::<i32>()))
+        .collect::<Vec<(u8, i32)>>();
+    let mut data = input.clone();
+    bisection::select_nth_by(&mut data, 100, |a, b| b.cmp(a));
+    let mut expect = input.clone();
+    expect.sort_by(|a, b| b.cmp(a));
+    assert_eq!(data[100], expect[100]);
+    let mut data = input.clone();
+    bisection::select_nth_by_key(&mut data, 250, |x| x.1);
+    expect.sort_by_key(|x| x.1);
+    assert_eq!(data[250], expect[250]);
+}
+
+#[test]
+fn test_select_nth_linear() {
+    let n = 100000;
+    let patterns: Vec<Vec<i32>> = vec![
+        (0 .. n).collect(),
+        (0 .. n).rev().collect(),
+        vec![7; n as usize],
+        (0 .. n).map(|x| x.min(n - x)).collect(),
+        (0 .. n).map(|x| x % 7).collect(),
+        (0 .. n).map(|x| if x % 2 == 0 { x } else { n - x }).collect(),
+        (0 .. n).map(|x| (x as i64 * 7919 % 100003) as i32).collect(),
+    ];
+    for input in patterns {
+        let mut expect = input.clone();
+        expect.sort();
+        for k in [0, n as usize / 2, n as usize - 1] {
+            let mut data = input.clone();
+            let mut count = 0usize;
+            bisection::select_nth_by(&mut data, k, |a, b| { count += 1; a.cmp(b) });
+            assert_eq!(data[k], expect[k]);
+            assert!(count <= 20 * n as usize, "{} comparisons", count);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_select_nth_out_of_bounds() {
+    bisection::select_nth(&mut [1, 2, 3], 3);
+}
+
+#[test]
+fn test_partial_sort() {
+    for input in inputs() {
+        let mut expect = input.clone();
+        expect.sort();
+        let n = input.len();
+        for k in [0, 1, n / 2, n, n + 1] {
+            let mut data = input.clone();
+            heap::partial_sort(&mut data, k);
+            let k = k.min(n);
+            assert_eq!(data[.. k], expect[.. k]);
+            data.sort();
+            assert_eq!(data, expect);
+        }
+        let mut data = input.clone();
+        heap::partial_sort_by(&mut data, 5, |a, b| b.cmp(a));
+        let k = n.min(5);
+        assert!(data[.. k].iter().eq(expect.iter().rev().take(k)));
+        let mut data = input.clone();
+        heap::partial_sort_by_key(&mut data, 5, |x| x.wrapping_neg());
+        let mut keys = input.iter().map(|x| x.wrapping_neg()).collect::<Vec<_>>();
+        keys.sort();
+        assert!(data[.. k].iter().map(|x| x.wrapping_neg()).eq(keys.into_iter().take(k)));
+    }
+}
+
+#[test]
+fn test_top_k() {
+    for input in inputs() {
+        let mut expect = input.clone();
+        expect.sort_by(|a, b| b.cmp(a));
+        for k in [0, 1, 7, input.len(), input.len() + 3] {
+            let top = heap::top_k(input.iter().copied(), k);
+            assert_eq!(top, expect[.. k.min(input.len())]);
+            let top = heap::top_k_by(input.iter().copied(), k, |a, b| b.cmp(a));
+            assert!(top.iter().eq(expect.iter().rev().take(k)));
+        }
+    }
+    // More items asked for than there are.
+    assert_eq!(heap::top_k(vec![3, 1, 2], usize::MAX), [3, 2, 1]);
+    assert_eq!(heap::top_k(vec![3, 1, 2], 1 << 40), [3, 2, 1]);
+    assert_eq!(heap::top_k((0 .. 5).filter(|x| x % 2 == 0), usize::MAX), [4, 2, 0]);
+    assert!(heap::top_k(Vec::<i32>::new(), usize::MAX).is_empty());
+    let words = ["pear", "fig", "banana", "kiwi", "apple"];
+    assert_eq!(heap::top_k_by_key(words, 2, |w| w.len()), ["banana", "apple"]);
+    let lines = (0 .. 1000).map(|x| format!("{:03}", x * 7 % 1000));
+    assert_eq!(heap::top_k(lines, 3), ["999", "998", "997"]);
+}
+
+#[test]
+fn test_select_nth_adversary() {
+    // McIlroy's adversary: values are decided lazily as the comparisons go,
+    // so as to make every pivot as bad as it can. Running again on the input
+    // it settled on replays the same comparisons.
+    let n = 1 << 15;
+    for k in [n / 2, n - 1] {
+        let gas = n;
+        let mut val = vec![gas; n];
+        let (mut solid, mut candidate) = (0, 0);
+        let mut data = (0 .. n).collect::<Vec<usize>>();
+        bisection::select_nth_by(&mut data, k, |&x, &y| {
+            if val[x] == gas && val[y] == gas {
+                let z = if x == candidate { x } else { y };
+                val[z] = solid;
+                solid += 1;
+            }
+            if val[x] == gas {
+                candidate = x;
+            } else if val[y] == gas {
+                candidate = y;
+            }
+            val[x].cmp(&val[y])
+        });
+        let mut count = 0;
+        let mut data = val.clone();
+        bisection::select_nth_by(&mut data, k, |a, b| { count += 1; a.cmp(b) });
+        let mut expect = val.clone();
+        expect.sort();
+        assert_eq!(data[k], expect[k]);
+        assert!(count <= 20 * n, "k: {}, {} comparisons", k, count);
+    }
+}
@@ -0,0 +1,42 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::small::{sort_small, sort_small_by, sort_small_by_key};
+
+fn check<const N: usize>(rng: &mut StdRng) {
+    for _ in 0 .. 100 {
+        let input: [i32; N] = std::array::from_fn(|_| rng.gen_range(0 .. 8));
+        let mut expect = input;
+        expect.sort();
+        let mut data = input;
+        sort_small(&mut data);
+        assert_eq!(data, expect, "input: {:?}", input);
+        let mut data = input.map(|x| x.to_string());
+        sort_small_by_key(&mut data, |s| s.parse::<i32>().unwrap());
+        assert_eq!(data, expect.map(|x| x.to_string()), "input: {:?}", input);
+        let mut data = input;
+        sort_small_by(&mut data, |a, b| b.cmp(a));
+        expect.reverse();
+        assert_eq!(data, expect, "input: {:?}", input);
+    }
+}
+
+#[test]
+fn test_sort_small() {
+    let mut rng = StdRng::seed_from_u64(0);
+    check::<0>(&mut rng);
+    check::<1>(&mut rng);
+    check::<2>(&mut rng);
+    check::<3>(&mut rng);
+    check::<4>(&mut rng);
+    check::<5>(&mut rng);
+    check::<6>(&mut rng);
+    check::<7>(&mut rng);
+    check::<8>(&mut rng);
+    check::<9>(&mut rng);
+    check::<10>(&mut rng);
+    check::<11>(&mut rng);
+    check::<12>(&mut rng);
+    check::<13>(&mut rng);
+    check::<14>(&mut rng);
+    check::<15>(&mut rng);
+    check::<16>(&mut rng);
+}
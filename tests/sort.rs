@@ -0,0 +1,354 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::{bitonic, heap, bisection};
+
+const LENS: &[usize] = &[0, 1, 2, 3, 4, 5, 7, 8, 9, 15, 16, 17, 100, 255, 256, 257, 1000];
+
+fn inputs() -> Vec<Vec<i32>> {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let mut ret = vec![];
+    for &len in LENS {
+        ret.push((0 .. len).map(|_| rng.gen()).collect());
+        ret.push((0 .. len).map(|_| rng.gen_range(0 .. 4)).collect());
+        ret.push((0 .. len as i32).collect());
+        ret.push((0 .. len as i32).rev().collect());
+    }
+    ret
+}
+
+fn check(sort: impl Fn(&mut [i32])) {
+    for input in inputs() {
+        let mut expect = input.clone();
+        expect.sort();
+        let mut data = input.clone();
+        sort(&mut data);
+        assert_eq!(data, expect, "input: {:?}", input);
+    }
+}
+
+#[test]
+fn test_quick_sort() { check(bisection::quick_sort); }
+#[test]
+fn test_intro_sort() { check(bisection::intro_sort); }
+#[test]
+fn test_pdq_sort() { check(bisection::pdq_sort); }
+#[test]
+fn test_merge_sort() { check(bisection::merge_sort); }
+#[test]
+fn test_tim_sort() { check(bisection::tim_sort); }
+#[test]
+fn test_heap_sort() { check(heap::heap_sort); }
+#[test]
+fn test_dary_heap_sort() {
+    check(heap::dary_heap_sort::<2, _>);
+    check(heap::dary_heap_sort::<3, _>);
+    check(heap::dary_heap_sort::<4, _>);
+    check(heap::dary_heap_sort::<8, _>);
+}
+#[test]
+fn test_weak_heap_sort() { check(heap::weak_heap_sort); }
+#[test]
+fn test_smooth_b_sort() { check(heap::smooth_b_sort); }
+#[test]
+fn test_smooth_l_sort() { check(heap::smooth_l_sort); }
+#[test]
+fn test_bitonic_r_sort() { check(bitonic::bitonic_r_sort); }
+#[test]
+fn test_bitonic_i_sort() { check(bitonic::bitonic_i_sort); }
+#[test]
+fn test_quick_p_sort() {
+    for t_depth in 0 ..= 3 {
+        check(|data| bisection::quick_p_sort(data, t_depth));
+    }
+}
+#[test]
+fn test_merge_p_sort() {
+    for t_depth in 0 ..= 3 {
+        check(|data| bisection::merge_p_sort(data, t_depth));
+    }
+}
+#[test]
+fn test_bitonic_rp_sort() {
+    for t_depth in 0 ..= 3 {
+        check(|data| bitonic::bitonic_rp_sort(data, t_depth));
+    }
+}
+#[test]
+fn test_bitonic_ip_sort() {
+    for t_depth in 0 ..= 3 {
+        check(|data| bitonic::bitonic_ip_sort(data, t_depth));
+    }
+}
+#[test]
+fn test_sample_p_sort() {
+    for t_depth in 0 ..= 3 {
+        check(|data| bitonic::sample_p_sort(data, t_depth));
+    }
+}
+
+#[test]
+fn test_floats() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let input = (0 .. 500).map(|_| rng.gen_range(-1e3 .. 1e3)).collect::<Vec<f64>>();
+    let mut expect = input.clone();
+    expect.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let sorts: &[fn(&mut [f64])] = &[
+        bisection::quick_sort, bisection::intro_sort, bisection::pdq_sort,
+        bisection::merge_sort, bisection::tim_sort,
+        heap::heap_sort, heap::weak_heap_sort,
+        heap::smooth_b_sort, heap::smooth_l_sort,
+        bitonic::bitonic_r_sort, bitonic::bitonic_i_sort,
+    ];
+    for sort in sorts {
+        let mut data = input.clone();
+        sort(&mut data);
+        assert_eq!(data, expect);
+    }
+}
+
+fn check_by(sort_by: impl Fn(&mut [i32], fn(&i32, &i32) -> std::cmp::Ordering),
+            sort_by_key: impl Fn(&mut [(u8, i32)], fn(&(u8, i32)) -> i32)) {
+    for input in inputs() {
+        let mut expect = input.clone();
+        expect.sort_by(|a, b| b.cmp(a));
+        let mut data = input.clone();
+        sort_by(&mut data, |a, b| b.cmp(a));
+        assert_eq!(data, expect, "input: {:?}", input);
+
+        let mut data = input.iter().map(|&x| (x as u8, x)).collect::<Vec<_>>();
+        sort_by_key(&mut data, |&(_, x)| x.wrapping_neg());
+        let keys = data.iter().map(|&(_, x)| x.wrapping_neg()).collect::<Vec<_>>();
+        let mut expect = keys.clone();
+        expect.sort();
+        assert_eq!(keys, expect, "input: {:?}", input);
+    }
+}
+
+#[test]
+fn test_quick_sort_by() {
+    check_by(|d, c| bisection::quick_sort_by(d, c), |d, k| bisection::quick_sort_by_key(d, k));
+}
+#[test]
+fn test_intro_sort_by() {
+    check_by(|d, c| bisection::intro_sort_by(d, c), |d, k| bisection::intro_sort_by_key(d, k));
+}
+#[test]
+fn test_pdq_sort_by() {
+    check_by(|d, c| bisection::pdq_sort_by(d, c), |d, k| bisection::pdq_sort_by_key(d, k));
+}
+#[test]
+fn test_merge_sort_by() {
+    check_by(|d, c| bisection::merge_sort_by(d, c), |d, k| bisection::merge_sort_by_key(d, k));
+}
+#[test]
+fn test_tim_sort_by() {
+    check_by(|d, c| bisection::tim_sort_by(d, c), |d, k| bisection::tim_sort_by_key(d, k));
+}
+#[test]
+fn test_heap_sort_by() {
+    check_by(|d, c| heap::heap_sort_by(d, c), |d, k| heap::heap_sort_by_key(d, k));
+}
+#[test]
+fn test_dary_heap_sort_by() {
+    check_by(|d, c| heap::dary_heap_sort_by::<4, _, _>(d, c),
+             |d, k| heap::dary_heap_sort_by_key::<4, _, _, _>(d, k));
+}
+#[test]
+fn test_weak_heap_sort_by() {
+    check_by(|d, c| heap::weak_heap_sort_by(d, c), |d, k| heap::weak_heap_sort_by_key(d, k));
+}
+#[test]
+fn test_smooth_b_sort_by() {
+    check_by(|d, c| heap::smooth_b_sort_by(d, c), |d, k| heap::smooth_b_sort_by_key(d, k));
+}
+#[test]
+fn test_smooth_l_sort_by() {
+    check_by(|d, c| heap::smooth_l_sort_by(d, c), |d, k| heap::smooth_l_sort_by_key(d, k));
+}
+#[test]
+fn test_bitonic_r_sort_by() {
+    check_by(|d, c| bitonic::bitonic_r_sort_by(d, c), |d, k| bitonic::bitonic_r_sort_by_key(d, k));
+}
+#[test]
+fn test_bitonic_i_sort_by() {
+    check_by(|d, c| bitonic::bitonic_i_sort_by(d, c), |d, k| bitonic::bitonic_i_sort_by_key(d, k));
+}
+#[test]
+fn test_quick_p_sort_by() {
+    check_by(|d, c| bisection::quick_p_sort_by(d, 2, c),
+             |d, k| bisection::quick_p_sort_by_key(d, 2, k));
+}
+#[test]
+fn test_merge_p_sort_by() {
+    check_by(|d, c| bisection::merge_p_sort_by(d, 2, c),
+             |d, k| bisection::merge_p_sort_by_key(d, 2, k));
+}
+#[test]
+fn test_bitonic_rp_sort_by() {
+    check_by(|d, c| bitonic::bitonic_rp_sort_by(d, 2, c),
+             |d, k| bitonic::bitonic_rp_sort_by_key(d, 2, k));
+}
+#[test]
+fn test_bitonic_ip_sort_by() {
+    check_by(|d, c| bitonic::bitonic_ip_sort_by(d, 2, c),
+             |d, k| bitonic::bitonic_ip_sort_by_key(d, 2, k));
+}
+#[test]
+fn test_sample_p_sort_by() {
+    check_by(|d, c| bitonic::sample_p_sort_by(d, 2, c),
+             |d, k| bitonic::sample_p_sort_by_key(d, 2, k));
+}
+
+#[test]
+fn test_quick_sort_patterns() {
+    let n = 100000;
+    let patterns: Vec<Vec<i32>> = vec![
+        (0 .. n).collect(),
+        (0 .. n).rev().collect(),
+        vec![7; n as usize],
+        (0 .. n).map(|x| x.min(n - x)).collect(),
+        (0 .. n).map(|x| x % 7).collect(),
+        (0 .. n).map(|x| if x % 2 == 0 { x } else { n - x }).collect(),
+        (0 .. n).map(|x| (x as i64 * 7919 % 100003) as i32).collect(),
+    ];
+    let bound = 3 * n as usize * (usize::BITS - (n as usize).leading_zeros()) as usize;
+    type SortBy = fn(&mut [i32], &mut dyn FnMut(&i32, &i32) -> std::cmp::Ordering);
+    let sorts: &[SortBy] = &[
+        |d, c| bisection::intro_sort_by(d, c),
+        |d, c| bisection::pdq_sort_by(d, c),
+    ];
+    for input in patterns {
+        let mut expect = input.clone();
+        expect.sort();
+        for sort in sorts {
+            let mut data = input.clone();
+            let mut count = 0usize;
+            sort(&mut data, &mut |a, b| { count += 1; a.cmp(b) });
+            assert_eq!(data, expect);
+            assert!(count <= bound, "{} comparisons", count);
+        }
+    }
+}
+
+#[test]
+fn test_tim_sort_runs() {
+    let n = 100000;
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut nearly_sorted = (0 .. n).collect::<Vec<i32>>();
+    for _ in 0 .. 10 {
+        nearly_sorted.swap(rng.gen_range(0 .. n as usize), rng.gen_range(0 .. n as usize));
+    }
+    let mut runs = vec![];
+    for k in 0 .. 8 {
+        let run = (0 .. n / 8).map(|x| x * 8 + k);
+        if k % 2 == 0 { runs.extend(run); } else { runs.extend(run.rev()); }
+    }
+    let patterns: Vec<(Vec<i32>, usize)> = vec![
+        ((0 .. n).collect(), n as usize),
+        ((0 .. n).rev().collect(), n as usize),
+        (vec![7; n as usize], n as usize),
+        (nearly_sorted, 4 * n as usize),
+        (runs, 5 * n as usize),
+    ];
+    for (input, bound) in patterns {
+        let mut expect = input.clone();
+        expect.sort();
+        let mut data = input.clone();
+        let mut count = 0usize;
+        bisection::tim_sort_by(&mut data, |a, b| { count += 1; a.cmp(b) });
+        assert_eq!(data, expect);
+        assert!(count <= bound, "{} comparisons", count);
+    }
+}
+
+#[test]
+fn test_tim_sort_stable() {
+    let mut rng = StdRng::seed_from_u64(8);
+    for &len in LENS.iter().chain(&[5000]) {
+        for range in [2, 16, 1000] {
+            let mut data = (0 .. len).map(|ind| (rng.gen_range(0 .. range), ind))
+                .collect::<Vec<(i32, usize)>>();
+            if range == 16 { data[.. len / 2].sort(); }
+            let mut expect = data.clone();
+            expect.sort();
+            bisection::tim_sort_by_key(&mut data, |x| x.0);
+            assert_eq!(data, expect);
+        }
+    }
+}
+
+#[test]
+fn test_bitonic_ip_sort_lengths() {
+    let mut rng = StdRng::seed_from_u64(9);
+    for len in 0 .. 300 {
+        let input = (0 .. len).map(|_| rng.gen_range(0 .. 50)).collect::<Vec<i32>>();
+        let mut expect = input.clone();
+        bitonic::bitonic_i_sort(&mut expect);
+        for t_depth in 0 ..= 4 {
+            let mut data = input.clone();
+            bitonic::bitonic_ip_sort(&mut data, t_depth);
+            assert_eq!(data, expect, "len: {}, t_depth: {}", len, t_depth);
+        }
+    }
+}
+
+#[test]
+fn test_merge_p_sort_large() {
+    let mut rng = StdRng::seed_from_u64(10);
+    for len in [8191, 8193, 50000] {
+        for range in [4, i32::MAX] {
+            let input = (0 .. len).map(|_| (rng.gen_range(0 .. range), rng.gen::<u16>()))
+                .collect::<Vec<(i32, u16)>>();
+            let mut expect = input.clone();
+            expect.sort_by_key(|x| x.0);
+            for t_depth in 1 ..= 4 {
+                let mut data = input.clone();
+                bisection::merge_p_sort_by_key(&mut data, t_depth, |x| x.0);
+                assert_eq!(data, expect, "len: {}, t_depth: {}", len, t_depth);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_sample_p_sort_large() {
+    use bitonic::BucketSort;
+    let mut rng = StdRng::seed_from_u64(11);
+    let buckets = [BucketSort::Quick, BucketSort::Intro, BucketSort::Pdq,
+                   BucketSort::Merge, BucketSort::Tim, BucketSort::Heap];
+    for len in [16385, 100000] {
+        for range in [1, 4, i32::MAX] {
+            let input = (0 .. len).map(|_| rng.gen_range(0 .. range)).collect::<Vec<i32>>();
+            let mut expect = input.clone();
+            expect.sort();
+            for t_depth in 1 ..= 4 {
+                for bucket_sort in buckets {
+                    let mut data = input.clone();
+                    bitonic::sample_p_sort_with(&mut data, t_depth, bucket_sort);
+                    assert_eq!(data, expect, "len: {}, t_depth: {}, {:?}", len, t_depth, bucket_sort);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_quick_p_sort_large() {
+    let mut rng = StdRng::seed_from_u64(12);
+    let mut inputs = vec![];
+    for len in [4097, 4099, 8193, 1 << 20] {
+        inputs.push((0 .. len).map(|_| rng.gen_range(0 .. 4)).collect::<Vec<i32>>());
+        inputs.push((0 .. len).collect());
+        inputs.push((0 .. len).rev().collect());
+        inputs.push(vec![7; len as usize]);
+    }
+    inputs.push((0 .. 100000).map(|_| rng.gen_range(0 .. i32::MAX)).collect());
+    for input in inputs {
+        let mut expect = input.clone();
+        expect.sort();
+        for t_depth in 1 ..= 4 {
+            let mut data = input.clone();
+            bisection::quick_p_sort(&mut data, t_depth);
+            assert_eq!(data, expect, "len: {}, t_depth: {}", input.len(), t_depth);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::sorter::{registry, radix_registry, Complexity};
+
+#[test]
+fn test_registry_sorts() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for len in [0, 1, 2, 5, 31, 64, 500] {
+        let input = (0 .. len).map(|_| rng.gen_range(-50 .. 50)).collect::<Vec<i64>>();
+        let mut expect = input.clone();
+        expect.sort();
+        for sorter in registry(2) {
+            let mut data = input.clone();
+            sorter.sort(&mut data);
+            assert_eq!(data, expect, "{}", sorter.name());
+        }
+    }
+}
+
+#[test]
+fn test_registry_metadata() {
+    let sorters = registry::<i64>(2);
+    let names = sorters.iter().map(|s| s.name()).collect::<HashSet<_>>();
+    assert_eq!(names.len(), sorters.len());
+    for sorter in &sorters {
+        let bitonic = sorter.name().starts_with("bitonic");
+        assert_eq!(sorter.worst_case() == Complexity::NLog2N, bitonic, "{}", sorter.name());
+        let parallel = ["bitonic_rp", "bitonic_ip", "merge_p", "quick_p", "sample_p"].contains(&sorter.name());
+        assert_eq!(sorter.parallel(), parallel, "{}", sorter.name());
+    }
+    assert_eq!(Complexity::NLog2N.to_string(), "O(n log² n)");
+}
+
+#[test]
+fn test_radix_registry() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let input = (0 .. 1000).map(|_| rng.gen()).collect::<Vec<i32>>();
+    let mut expect = input.clone();
+    expect.sort();
+    for sorter in radix_registry() {
+        let mut data = input.clone();
+        sorter.sort(&mut data);
+        assert_eq!(data, expect, "{}", sorter.name());
+        assert_eq!(sorter.worst_case(), Complexity::N, "{}", sorter.name());
+        assert!(!sorter.parallel(), "{}", sorter.name());
+    }
+    assert_eq!(Complexity::N.to_string(), "O(n)");
+}
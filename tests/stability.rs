@@ -0,0 +1,102 @@
+use std::cmp::Ordering;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::{bisection, radix};
+use sort_algo::sorter::{registry, radix_registry};
+
+// A key with its original index, ordered by the key only.
+#[derive(Clone, Copy, Debug)]
+struct Pair {
+    key   : u8,
+    index : usize,
+}
+
+impl PartialEq for Pair {
+    fn eq(&self, other: &Self) -> bool { self.key == other.key }
+}
+impl PartialOrd for Pair {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { self.key.partial_cmp(&other.key) }
+}
+
+const LENS: &[usize] = &[0, 1, 2, 3, 16, 17, 100, 257, 1000, 4096];
+// Beyond the sequential threshold of `merge_p_sort`, so that its parallel
+// merges run.
+const LONG_LENS: &[usize] = &[4097, 10000, 50000];
+
+// Inputs of lengths `lens` with many duplicate keys, random or made of runs.
+fn inputs(lens: &[usize]) -> Vec<Vec<Pair>> {
+    let mut rng = StdRng::seed_from_u64(0x57ab);
+    let mut ret = vec![];
+    for &len in lens {
+        for range in [1, 2, 5, 50] {
+            let keys = (0 .. len).map(|_| rng.gen_range(0 .. range)).collect::<Vec<u8>>();
+            let mut runs = keys.clone();
+            runs[.. len / 2].sort();
+            runs[len / 2 ..].sort_by(|a, b| b.cmp(a));
+            for keys in [keys, runs] {
+                ret.push(keys.into_iter().enumerate().map(|(index, key)| Pair { key, index })
+                    .collect());
+            }
+        }
+    }
+    ret
+}
+
+// Returns whether `data` is sorted by key, and whether equal keys kept the
+// order of their indices.
+fn check(lens: &[usize], sort: impl Fn(&mut [Pair])) -> (bool, bool) {
+    let (mut sorted, mut stable) = (true, true);
+    for input in inputs(lens) {
+        let mut data = input.clone();
+        sort(&mut data);
+        let mut indices = data.iter().map(|x| x.index).collect::<Vec<_>>();
+        indices.sort();
+        assert!(indices.into_iter().eq(0 .. input.len()));
+        for w in data.windows(2) {
+            sorted &= w[0].key <= w[1].key;
+            stable &= w[0].key != w[1].key || w[0].index < w[1].index;
+        }
+    }
+    (sorted, stable)
+}
+
+#[test]
+fn test_registry_stability() {
+    for sorter in registry::<Pair>(2) {
+        let (sorted, stable) = check(LENS, |d| sorter.sort(d));
+        assert!(sorted, "{}", sorter.name());
+        if sorter.stable() {
+            assert!(stable, "{} is tagged stable", sorter.name());
+        }
+    }
+    let stable = registry::<Pair>(2).into_iter().filter(|s| s.stable()).map(|s| s.name())
+        .collect::<Vec<_>>();
+    assert_eq!(stable, ["merge", "merge_p", "tim"]);
+    let stable = radix_registry::<u8>().into_iter().filter(|s| s.stable()).map(|s| s.name())
+        .collect::<Vec<_>>();
+    assert_eq!(stable, ["lsd_radix"]);
+}
+
+#[test]
+fn test_stable_by() {
+    let sorts: &[fn(&mut [Pair])] = &[
+        |d| bisection::merge_sort_by(d, |a, b| a.key.cmp(&b.key)),
+        |d| bisection::merge_sort_by_key(d, |x| x.key),
+        |d| bisection::merge_p_sort_by(d, 2, |a, b| a.key.cmp(&b.key)),
+        |d| bisection::tim_sort_by(d, |a, b| a.key.cmp(&b.key)),
+        |d| bisection::tim_sort_by_key(d, |x| x.key),
+        |d| radix::lsd_radix_sort_by_key(d, |x| x.key),
+    ];
+    for sort in sorts {
+        assert_eq!(check(LENS, sort), (true, true));
+    }
+}
+
+#[test]
+fn test_merge_p_sort_stability() {
+    for t_depth in 1 ..= 3 {
+        assert_eq!(check(LONG_LENS, |d| bisection::merge_p_sort(d, t_depth)), (true, true),
+                   "t_depth: {}", t_depth);
+        assert_eq!(check(LONG_LENS, |d| bisection::merge_p_sort_by_key(d, t_depth, |x| x.key)),
+                   (true, true), "t_depth: {}", t_depth);
+    }
+}
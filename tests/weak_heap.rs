@@ -0,0 +1,97 @@
+mod common;
+
+use std::cell::Cell;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use sort_algo::heap::{self, WeakHeap};
+use common::{MaxHeap, Counted, check_interleaved};
+
+impl MaxHeap for WeakHeap<i32> {
+    fn push(&mut self, x: i32) { WeakHeap::push(self, x); }
+    fn pop(&mut self) -> Option<i32> { WeakHeap::pop(self) }
+    fn peek(&self) -> Option<&i32> { WeakHeap::peek(self) }
+    fn len(&self) -> usize { WeakHeap::len(self) }
+}
+
+#[test]
+fn test_weak_heap() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for n in [0, 1, 2, 3, 4, 5, 8, 9, 15, 64, 65, 100, 1000] {
+        let input = (0 .. n).map(|_| rng.gen_range(0 .. 50)).collect::<Vec<i32>>();
+        let mut expect = input.clone();
+        expect.sort();
+        assert_eq!(WeakHeap::from_vec(input.clone()).into_sorted_vec(), expect, "n: {}", n);
+        check_interleaved::<WeakHeap<i32>>(&input, &mut rng);
+    }
+}
+
+#[test]
+fn test_weak_heap_reverse_bits() {
+    // The reverse bits are packed 64 to a word. Grow the heap across word
+    // boundaries, shrink it back below them, which leaves stale bits behind,
+    // and grow it again, with ascending elements, which all climb to the root
+    // and flip bits on the way, and with random ones.
+    let mut rng = StdRng::seed_from_u64(0);
+    for start in [0, 1, 63, 64, 65, 200] {
+        let input = (0 .. start).map(|_| rng.gen_range(0 .. 1000)).collect::<Vec<i32>>();
+        let mut heap = WeakHeap::from_vec(input.clone());
+        let mut reference = input;
+        let mut next = 0;
+        for (grow, shrink) in [(70, 100), (130, 60), (1, 5), (300, 250), (64, 0)] {
+            for ind in 0 .. grow {
+                let x = if ind % 2 == 0 { next += 1; next } else { rng.gen_range(0 .. 1000) };
+                heap.push(x);
+                reference.push(x);
+            }
+            reference.sort();
+            for _ in 0 .. shrink {
+                assert_eq!(heap.pop(), reference.pop(), "start: {}", start);
+            }
+            assert_eq!(heap.len(), reference.len());
+            assert_eq!(heap.peek(), reference.last());
+        }
+        assert_eq!(heap.into_sorted_vec(), reference, "start: {}", start);
+    }
+}
+
+#[test]
+fn test_weak_heap_merge() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for (n1, n2) in [(0, 0), (0, 10), (10, 0), (100, 7), (7, 100), (200, 300)] {
+        let input1 = (0 .. n1).map(|_| rng.gen::<i32>()).collect::<Vec<_>>();
+        let input2 = (0 .. n2).map(|_| rng.gen::<i32>()).collect::<Vec<_>>();
+        let mut heap = WeakHeap::from_vec(input1.clone());
+        heap.merge(input2.clone().into());
+        let mut expect = [input1, input2].concat();
+        expect.sort();
+        assert_eq!(heap.into_sorted_vec(), expect, "n1: {}, n2: {}", n1, n2);
+    }
+}
+
+#[test]
+fn test_weak_heap_sort_comparisons() {
+    // At most `n ⌈log n⌉ - 2^⌈log n⌉ + n - 1` comparisons, for the sort and
+    // for a heap built and then sorted.
+    let mut rng = StdRng::seed_from_u64(0);
+    for n in [2usize, 3, 10, 100, 1000, 1024, 1025, 10000] {
+        let log = n.next_power_of_two().trailing_zeros() as usize;
+        let bound = n * log - (1 << log) + n - 1;
+        for _ in 0 .. 5 {
+            let input = (0 .. n).map(|_| rng.gen::<u32>()).collect::<Vec<_>>();
+            let count = Cell::new(0);
+            let mut data = input.clone();
+            heap::weak_heap_sort_by(&mut data, |a, b| {
+                count.set(count.get() + 1);
+                a.cmp(b)
+            });
+            assert!(data.windows(2).all(|w| w[0] <= w[1]));
+            assert!(count.get() <= bound, "n: {}, comparisons: {} > {}", n, count.get(), bound);
+
+            let count = Cell::new(0);
+            let data = input.iter().map(|&x| Counted(x, &count)).collect();
+            let data = WeakHeap::from_vec(data).into_sorted_vec();
+            assert!(data.windows(2).all(|w| w[0].0 <= w[1].0));
+            assert!(count.get() <= bound, "n: {}, heap comparisons: {} > {}", n, count.get(),
+                    bound);
+        }
+    }
+}
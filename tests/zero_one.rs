@@ -0,0 +1,68 @@
+use sort_algo::bitonic::{self, SortingNetwork, verify_zero_one, verify_zero_one_random};
+
+// Exhaustive up to these lengths, serial and parallel, random beyond.
+const EXHAUSTIVE: [usize; 2] = [14, 10];
+const RANDOM: [usize; 6] = [31, 33, 100, 127, 129, 1000];
+
+#[test]
+fn test_bitonic_networks() {
+    for n in 0 ..= 16 {
+        assert_eq!(SortingNetwork::bitonic(n).verify_exhaustive(), Ok(()), "n: {}", n);
+    }
+    for n in RANDOM {
+        assert_eq!(SortingNetwork::bitonic(n).verify_random(1 << 10, 0), Ok(()), "n: {}", n);
+    }
+}
+
+#[test]
+fn test_optimal_networks() {
+    for n in 0 ..= 16 {
+        assert_eq!(SortingNetwork::optimal(n).verify_exhaustive(), Ok(()), "n: {}", n);
+    }
+}
+
+#[test]
+fn test_bitonic_sorts() {
+    type Sort = fn(&mut [bool]);
+    let sorts: &[(&str, Sort, bool)] = &[
+        ("bitonic_r", bitonic::bitonic_r_sort, false),
+        ("bitonic_rp", |d| bitonic::bitonic_rp_sort(d, 2), true),
+        ("bitonic_i", bitonic::bitonic_i_sort, false),
+        ("bitonic_ip", |d| bitonic::bitonic_ip_sort(d, 2), true),
+    ];
+    for &(name, sort, parallel) in sorts {
+        for n in 0 ..= EXHAUSTIVE[parallel as usize] {
+            assert_eq!(verify_zero_one(n, sort), Ok(()), "{}, n: {}", name, n);
+        }
+        for n in RANDOM {
+            assert_eq!(verify_zero_one_random(n, 50, 0, sort), Ok(()), "{}, n: {}", name, n);
+        }
+    }
+}
+
+#[test]
+fn test_user_network() {
+    // The optimal network on 4 wires, with a comparator missing.
+    let layers = vec![vec![(0, 1), (2, 3)], vec![(0, 2), (1, 3)], vec![(1, 2)]];
+    assert_eq!(SortingNetwork::new(4, layers.clone()).verify(), Ok(()));
+    let broken = SortingNetwork::new(4, layers[.. 2].to_vec());
+    let input = broken.verify().unwrap_err();
+    let mut data = input.clone();
+    broken.apply(&mut data);
+    assert!(data.windows(2).any(|w| w[0] && !w[1]), "{:?}", input);
+
+    // A bitonic network with its last comparator dropped.
+    for n in [9, 40] {
+        let mut layers = SortingNetwork::bitonic(n).layers().to_vec();
+        layers.last_mut().unwrap().pop();
+        let broken = SortingNetwork::new(n, layers);
+        assert!(broken.verify().is_err(), "n: {}", n);
+    }
+}
+
+#[test]
+fn test_broken_sort() {
+    let input = verify_zero_one(5, |d| d[.. 4].sort()).unwrap_err();
+    assert!(!input[4] && input.contains(&true));
+    assert!(verify_zero_one_random(50, 100, 0, |d| d[1 ..].sort()).is_err());
+}